@@ -13,12 +13,12 @@ path="day$day"
 
 cp -a "skeleton" "$path"
 
-sed -i -e "s/dayXX/day$day/" "${path}/Cargo.toml"
-sed -i -e "s/dayXX/day$day/" "${path}/src/main.rs"
+sed -i -e "s/name = \"skeleton\"/name = \"day$day\"/" "${path}/Cargo.toml"
+sed -i -e "s/dayXX/day$day/" -e "s/use skeleton::/use day$day::/" "${path}/src/main.rs"
 
 sed -i -e "s/#\\(\"day$day\\)/\\1/" "Cargo.toml"
 
-mkdir "$path/data"
+mkdir -p "$path/data"
 touch "$path/data/test1.txt"
 touch "$path/data/test2.txt"
 touch "$path/data/input.txt"
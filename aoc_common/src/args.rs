@@ -0,0 +1,298 @@
+//! Command line flags shared by all days.
+
+use crate::{AOCError, AOCResult, load_input};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Where to read the puzzle input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `dayXX/data/input.txt`, relative to the current directory
+    #[default]
+    Default,
+    File(PathBuf),
+    /// Given as `-` on the command line
+    Stdin,
+}
+
+impl InputSource {
+    /// Read the whole input, where `day` is the name of the crate (used to
+    /// locate the default input).
+    pub fn read(&self, day: &str) -> AOCResult<String> {
+        match self {
+            InputSource::Default => {
+                let mut input_file = std::env::current_dir().map_err(|e| AOCError::IOError {
+                    source: e,
+                    path: None,
+                })?;
+                input_file.push(day);
+                input_file.push("data");
+                input_file.push("input.txt");
+                load_input(input_file)
+            }
+            InputSource::File(path) => load_input(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|source| AOCError::IOError { source, path: None })?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// How to print the results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Results that can be printed as JSON, i.e. integers and (nested) sequences
+/// of them.
+pub trait JsonResult {
+    fn to_json(&self) -> String;
+}
+
+macro_rules! impl_json_result_int {
+    ($($int:ty),*) => {
+        $(
+            impl JsonResult for $int {
+                fn to_json(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_json_result_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+fn json_array<'a, T: JsonResult + 'a>(items: impl IntoIterator<Item = &'a T>) -> String {
+    let items: Vec<_> = items.into_iter().map(JsonResult::to_json).collect();
+    format!("[{}]", items.join(", "))
+}
+
+impl<T: JsonResult> JsonResult for [T] {
+    fn to_json(&self) -> String {
+        json_array(self)
+    }
+}
+
+impl<T: JsonResult> JsonResult for Vec<T> {
+    fn to_json(&self) -> String {
+        json_array(self)
+    }
+}
+
+/// Sorted, for a stable output
+impl<T: JsonResult + Ord> JsonResult for HashSet<T> {
+    fn to_json(&self) -> String {
+        let mut items: Vec<_> = self.iter().collect();
+        items.sort_unstable();
+        json_array(items)
+    }
+}
+
+impl<A: JsonResult, B: JsonResult> JsonResult for (A, B) {
+    fn to_json(&self) -> String {
+        format!("[{}, {}]", self.0.to_json(), self.1.to_json())
+    }
+}
+
+/// The flags common to all days.
+///
+/// ```text
+/// dayXX [INPUT] [-v|--verbose] [--part 1|2] [--format text|json] [OTHER...]
+/// ```
+///
+/// `INPUT` is a file path, or `-` for stdin. Any other option starting with a
+/// `-` is collected into `extra` together with the values following it, such
+/// that individual days can handle their own flags. Thus, a file `INPUT` needs
+/// to precede such options.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AocArgs {
+    pub input: InputSource,
+    pub part: Option<u8>,
+    pub format: OutputFormat,
+    pub verbose: bool,
+    pub extra: Vec<String>,
+}
+
+impl AocArgs {
+    /// Parse the arguments of the current process.
+    pub fn parse() -> AOCResult<Self> {
+        Self::from_args(std::env::args().skip(1))
+    }
+
+    /// Parse `args`, which shouldn't include the program name.
+    pub fn from_args<I, S>(args: I) -> AOCResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let usage = |msg: &str| AOCError::ParseError {
+            msg: format!("usage: {}", msg).into(),
+        };
+
+        let mut result = AocArgs::default();
+        let mut input = None;
+        let mut in_extra = false;
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            if in_extra && !arg.starts_with('-') {
+                result.extra.push(arg);
+                continue;
+            }
+            in_extra = false;
+            match arg.as_str() {
+                "-v" | "--verbose" => result.verbose = true,
+                "--part" => {
+                    let part = args.next().and_then(|p| p.parse().ok());
+                    match part {
+                        Some(part @ (1 | 2)) => result.part = Some(part),
+                        _ => return Err(usage("--part 1|2")),
+                    }
+                }
+                "--format" => {
+                    result.format = match args.next().as_deref() {
+                        Some("text") => OutputFormat::Text,
+                        Some("json") => OutputFormat::Json,
+                        _ => return Err(usage("--format text|json")),
+                    }
+                }
+                _ if arg.starts_with('-') && arg != "-" => {
+                    result.extra.push(arg);
+                    in_extra = true;
+                }
+                "-" if input.is_none() => input = Some(InputSource::Stdin),
+                _ if input.is_none() => input = Some(InputSource::File(arg.into())),
+                _ => return Err(usage("only a single input can be given")),
+            }
+        }
+        result.input = input.unwrap_or_default();
+
+        Ok(result)
+    }
+
+    /// Whether `part` should be solved, i.e. no `--part` was given or it
+    /// matches.
+    pub fn wants_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// The values following the day-specific option `name`, if it was given.
+    pub fn extra_values(&self, name: &str) -> Option<&[String]> {
+        let idx = self.extra.iter().position(|arg| arg == name)?;
+        let values = &self.extra[idx + 1..];
+        let len = values.iter().position(|arg| arg.starts_with('-')).unwrap_or(values.len());
+        Some(&values[..len])
+    }
+
+    /// Print the result of `part` in the selected format.
+    pub fn print_result(&self, part: u8, result: impl Debug + JsonResult) {
+        println!("{}", self.format_result(part, &result));
+    }
+
+    fn format_result(&self, part: u8, result: &(impl Debug + JsonResult)) -> String {
+        match self.format {
+            OutputFormat::Text => format!("Part {}: {:?}", part, result),
+            OutputFormat::Json => format!("{{\"part\": {}, \"result\": {}}}", part, result.to_json()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn defaults() -> AOCResult<()> {
+        let args = AocArgs::from_args(Vec::<String>::new())?;
+        assert_eq!(args, AocArgs::default());
+        assert_eq!(args.input, InputSource::Default);
+        assert!(args.wants_part(1) && args.wants_part(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn common_flags() -> AOCResult<()> {
+        let args = AocArgs::from_args(["in.txt", "--part", "2", "-v", "--format", "json"])?;
+        assert_eq!(
+            args,
+            AocArgs {
+                input: InputSource::File("in.txt".into()),
+                part: Some(2),
+                format: OutputFormat::Json,
+                verbose: true,
+                extra: vec![],
+            }
+        );
+        assert!(!args.wants_part(1) && args.wants_part(2));
+
+        let args = AocArgs::from_args(["--verbose", "-"])?;
+        assert_eq!(args.input, InputSource::Stdin);
+        assert!(args.verbose);
+
+        Ok(())
+    }
+
+    #[test]
+    fn extra() -> AOCResult<()> {
+        let args = AocArgs::from_args(["--race", "7", "9", "--dir", "x", "-v", "-"])?;
+        assert_eq!(args.input, InputSource::Stdin);
+        assert!(args.verbose);
+        assert_eq!(args.extra, vec!["--race", "7", "9", "--dir", "x"]);
+        assert_eq!(args.extra_values("--race"), Some(&["7".into(), "9".into()][..]));
+        assert_eq!(args.extra_values("--dir"), Some(&["x".into()][..]));
+        assert_eq!(args.extra_values("--other"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn format_result() -> AOCResult<()> {
+        let args = AocArgs::from_args(["--format", "json"])?;
+        assert_eq!(args.format_result(1, &42u64), r#"{"part": 1, "result": 42}"#);
+        assert_eq!(args.format_result(2, &-7i64), r#"{"part": 2, "result": -7}"#);
+        assert_eq!(
+            args.format_result(1, &(13i64, vec![8i64, 2, 2])),
+            r#"{"part": 1, "result": [13, [8, 2, 2]]}"#
+        );
+        let set: HashSet<u64> = [35, 13, 82].into_iter().collect();
+        assert_eq!(
+            args.format_result(1, &(13u64, set)),
+            r#"{"part": 1, "result": [13, [13, 35, 82]]}"#
+        );
+
+        let args = AocArgs::default();
+        assert_eq!(args.format_result(1, &(13i64, vec![8i64, 2])), "Part 1: (13, [8, 2])");
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        for args in [
+            &["--part"][..],
+            &["--part", "3"],
+            &["--part", "x"],
+            &["--format", "yaml"],
+            &["a.txt", "b.txt"],
+            &["-", "-"],
+            &["--part", "1", "a.txt", "--dir", "x", "-v", "y"],
+        ] {
+            assert!(
+                matches!(AocArgs::from_args(args.iter().copied()), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                args
+            );
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Count how often each value occurs.
+#[derive(Clone, Debug)]
+pub struct Counter<T> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T> Default for Counter<T> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, value: T) {
+        *self.counts.entry(value).or_default() += 1;
+    }
+
+    /// How often `value` was added, 0 if never.
+    pub fn count(&self, value: &T) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// The count of the most frequent value, 0 if the counter is empty.
+    pub fn max_count(&self) -> usize {
+        self.counts.values().copied().max().unwrap_or(0)
+    }
+
+    /// All values that were added exactly `n` times, in arbitrary order.
+    pub fn values_with_count(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.counts
+            .iter()
+            .filter(move |(_, count)| **count == n)
+            .map(|(value, _)| value)
+    }
+
+    /// The number of distinct values.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        iter.into_iter().for_each(|value| counter.add(value));
+        counter
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.count(&'a'), 5);
+        assert_eq!(counter.count(&'b'), 2);
+        assert_eq!(counter.count(&'d'), 1);
+        assert_eq!(counter.count(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.max_count(), 5);
+
+        counter.add('z');
+        assert_eq!(counter.count(&'z'), 1);
+        assert_eq!(counter.len(), 6);
+    }
+
+    #[test]
+    fn values_with_count() {
+        let counter: Counter<char> = "abracadabra".chars().collect();
+
+        let mut twice: Vec<_> = counter.values_with_count(2).copied().collect();
+        twice.sort_unstable();
+        assert_eq!(twice, vec!['b', 'r']);
+
+        let mut once: Vec<_> = counter.values_with_count(1).copied().collect();
+        once.sort_unstable();
+        assert_eq!(once, vec!['c', 'd']);
+
+        assert_eq!(counter.values_with_count(3).count(), 0);
+    }
+
+    #[test]
+    fn empty() {
+        let counter: Counter<u8> = Counter::new();
+        assert!(counter.is_empty());
+        assert_eq!(counter.max_count(), 0);
+        assert_eq!(counter.count(&1), 0);
+        assert_eq!(counter.values_with_count(0).count(), 0);
+    }
+}
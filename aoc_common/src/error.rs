@@ -0,0 +1,30 @@
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AOCError {
+    #[error("Failed to read input: {path:?}")]
+    IOError {
+        source: std::io::Error,
+        path: Option<PathBuf>,
+    },
+
+    #[error("Failed to parse input {msg}")]
+    ParseError { msg: Cow<'static, str> },
+
+    #[error("This part of the puzzle is not yet implemented")]
+    NotYetSolved,
+
+    #[error("No solution found: {msg}")]
+    NoSolution { msg: Cow<'static, str> },
+
+    #[error("Integer overflow")]
+    Overflow,
+
+    #[error("Timed out after {after:?}")]
+    Timeout { after: Duration },
+}
+
+pub type AOCResult<T> = Result<T, AOCError>;
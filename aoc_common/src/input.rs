@@ -0,0 +1,20 @@
+use crate::{AOCError, AOCResult};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+pub fn load_input(path: impl AsRef<Path>) -> AOCResult<String> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|source| AOCError::IOError {
+        source,
+        path: Some(path.into()),
+    })
+}
+
+pub trait FromFile<D: FromStr<Err = AOCError>> {
+    fn from_file(path: impl AsRef<Path>) -> AOCResult<D> {
+        load_input(path)?.parse::<D>()
+    }
+}
+
+impl<D: FromStr<Err = AOCError>> FromFile<D> for D {}
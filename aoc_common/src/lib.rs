@@ -0,0 +1,23 @@
+//! Helpers shared between the individual days.
+
+mod args;
+mod counter;
+mod error;
+mod input;
+pub mod memoize;
+pub mod parse;
+pub mod point;
+pub mod seq;
+mod timeout;
+#[cfg(feature = "trace")]
+mod trace;
+
+pub use args::{AocArgs, InputSource, JsonResult, OutputFormat};
+pub use counter::Counter;
+pub use error::{AOCError, AOCResult};
+pub use input::{FromFile, load_input};
+pub use memoize::Memo;
+pub use point::Point;
+pub use timeout::solve_with_timeout;
+#[cfg(feature = "trace")]
+pub use trace::init_tracing;
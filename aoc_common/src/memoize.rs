@@ -0,0 +1,95 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Cache for memoized recursion.
+///
+/// The closure passed to `get_or_compute` receives the `Memo` itself, such
+/// that it can recurse into other keys. Recursing into a key that is still
+/// being computed would never terminate, so this panics instead.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    pending: HashSet<K>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            pending: HashSet::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        if !self.pending.insert(key.clone()) {
+            panic!("reentrant computation of a memoized value (cyclic dependency)");
+        }
+        let value = f(self, &key);
+        self.pending.remove(&key);
+
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64, calls: &mut usize) -> u64 {
+        memo.get_or_compute(n, |memo, &n| {
+            *calls += 1;
+            if n < 2 {
+                n
+            } else {
+                fib(memo, n - 1, calls) + fib(memo, n - 2, calls)
+            }
+        })
+    }
+
+    #[test]
+    fn computes_each_key_once() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+
+        assert_eq!(fib(&mut memo, 50, &mut calls), 12586269025);
+        assert_eq!(calls, 51);
+        assert_eq!(memo.len(), 51);
+
+        // Everything is cached now
+        assert_eq!(fib(&mut memo, 50, &mut calls), 12586269025);
+        assert_eq!(calls, 51);
+    }
+
+    #[test]
+    #[should_panic(expected = "reentrant")]
+    fn detects_cycles() {
+        fn cyclic(memo: &mut Memo<u8, u8>, n: u8) -> u8 {
+            memo.get_or_compute(n, |memo, &n| cyclic(memo, (n + 1) % 3))
+        }
+
+        cyclic(&mut Memo::new(), 0);
+    }
+}
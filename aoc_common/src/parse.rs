@@ -0,0 +1,115 @@
+use crate::{AOCError, AOCResult};
+
+/// Split the input into blocks separated by blank lines.
+///
+/// Blank lines at the start or end of the input are ignored, as are repeated
+/// blank lines between blocks. Both `\n` and `\r\n` line endings are
+/// supported. The blocks don't include their final line break.
+pub fn split_blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut block_start = None;
+    let mut block_end = 0;
+
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            if let Some(start) = block_start.take() {
+                blocks.push(&input[start..block_end]);
+            }
+        } else {
+            block_start.get_or_insert(offset);
+            block_end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = block_start {
+        blocks.push(&input[start..block_end]);
+    }
+
+    blocks
+}
+
+/// Read a rectangular grid of characters, one row per line.
+///
+/// Trailing line breaks are ignored, but all other lines (including empty
+/// ones) are rows, and must have the same length.
+pub fn read_grid(input: &str) -> AOCResult<Vec<Vec<char>>> {
+    let grid: Vec<Vec<char>> = input
+        .trim_end_matches(['\n', '\r'])
+        .lines()
+        .map(|l| l.chars().collect())
+        .collect();
+
+    if let Some(first) = grid.first() {
+        if let Some((i, row)) = grid.iter().enumerate().find(|(_, row)| row.len() != first.len()) {
+            return Err(AOCError::ParseError {
+                msg: format!(
+                    "ragged grid: line {} has length {}, expected {}",
+                    i + 1,
+                    row.len(),
+                    first.len()
+                )
+                .into(),
+            });
+        }
+    }
+
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn blocks() {
+        assert_eq!(split_blocks("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(split_blocks("a\n\n\n\nb"), vec!["a", "b"]);
+        assert_eq!(split_blocks(""), Vec::<&str>::new());
+        assert_eq!(split_blocks("\n\n"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn leading_and_trailing_blank_lines() {
+        assert_eq!(split_blocks("\n\na\nb\n\nc\n\n\n"), vec!["a\nb", "c"]);
+        assert_eq!(split_blocks("  \na\n \t\nc\n  "), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn crlf() {
+        assert_eq!(
+            split_blocks("a\r\nb\r\n\r\nc\r\n"),
+            vec!["a\r\nb", "c"]
+        );
+        assert_eq!(
+            split_blocks("\r\na\r\n\r\n\r\nc"),
+            vec!["a", "c"]
+        );
+        let blocks = split_blocks("a\r\nb\r\n\r\nc\r\n");
+        assert_eq!(blocks[0].lines().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn grid() -> AOCResult<()> {
+        let expected = vec![vec!['a', 'b'], vec!['c', 'd']];
+        assert_eq!(read_grid("ab\ncd")?, expected);
+        assert_eq!(read_grid("ab\ncd\n\n")?, expected);
+        assert_eq!(read_grid("ab\r\ncd\r\n")?, expected);
+        assert_eq!(read_grid("")?, Vec::<Vec<char>>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ragged_grid() {
+        for input in ["ab\nc\n", "a\nbc\n", "ab\n\ncd\n"] {
+            assert!(
+                matches!(read_grid(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::ops::{Add, Sub};
+
+/// A location on a 2D grid, `x` is the column and `y` the row.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// The four orthogonally adjacent points.
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        [(0, -1), (-1, 0), (1, 0), (0, 1)]
+            .into_iter()
+            .map(move |(dx, dy)| self + Point::new(dx, dy))
+    }
+
+    /// The eight orthogonally or diagonally adjacent points.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| Point::new(dx, dy)))
+            .filter(|d| *d != Point::default())
+            .map(move |d| self + d)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let p = Point::new(3, -2);
+        let q = Point::new(-1, 5);
+        assert_eq!(p + q, Point::new(2, 3));
+        assert_eq!(p - q, Point::new(4, -7));
+        assert_eq!(p + q - q, p);
+        assert_eq!(Point::from((3, -2)), p);
+    }
+
+    #[test]
+    fn neighbors() {
+        let p = Point::new(1, 1);
+
+        let n4: Vec<_> = p.neighbors4().collect();
+        assert_eq!(
+            n4,
+            vec![
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(2, 1),
+                Point::new(1, 2),
+            ]
+        );
+
+        let mut n8: Vec<_> = p.neighbors8().collect();
+        assert_eq!(n8.len(), 8);
+        assert!(!n8.contains(&p));
+        n8.sort();
+        n8.dedup();
+        assert_eq!(n8.len(), 8);
+        assert!(n4.iter().all(|n| n8.contains(n)));
+        assert!(n8.iter().all(|n| (n.x - p.x).abs() <= 1 && (n.y - p.y).abs() <= 1));
+    }
+}
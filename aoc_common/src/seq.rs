@@ -0,0 +1,247 @@
+//! Extrapolation of integer sequences by finite differences.
+
+use crate::{AOCError, AOCResult};
+use std::ops::{Add, Sub};
+
+/// Integer types that sequences can be made of.
+pub trait SeqInt: Copy + Default + PartialEq + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Default + PartialEq + Add<Output = T> + Sub<Output = T>> SeqInt for T {}
+
+/// When to stop taking differences.
+///
+/// Taking differences until reaching a row of all zeros is the textbook
+/// approach. Stopping one level earlier at a constant row yields the same
+/// extrapolation (the differences of a constant row are all zero), so
+/// `AllEqual` is the default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StopCondition {
+    #[default]
+    AllEqual,
+    AllZero,
+}
+
+impl StopCondition {
+    fn is_base<T: SeqInt>(self, data: &[T]) -> bool {
+        match self {
+            StopCondition::AllEqual => data.windows(2).all(|w| w[0] == w[1]),
+            StopCondition::AllZero => data.iter().all(|x| *x == T::default()),
+        }
+    }
+}
+
+/// The differences between consecutive values, one less than `data`.
+pub fn differences<T: SeqInt>(data: &[T]) -> Vec<T> {
+    data.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+// Check that reaching the base row `row` (with `parent` being the row it was
+// computed from, if any) means that the differences actually stabilized.
+//
+// An empty row trivially satisfies the stop conditions, and so does a single
+// value for `AllEqual`. Reaching them means that the differences never
+// stabilized (e.g. for exponential data), and the extrapolation would be
+// meaningless. A single value is fine if it's the difference of a constant
+// row though, which is how `AllZero` ends for polynomial data.
+fn check_converged<T: SeqInt>(row: &[T], parent: Option<&[T]>) -> AOCResult<()> {
+    let converged = match row.len() {
+        0 => false,
+        1 => parent.is_some_and(|parent| StopCondition::AllEqual.is_base(parent)),
+        _ => true,
+    };
+    if !converged {
+        return Err(AOCError::ParseError {
+            msg: "sequence doesn't stabilize".into(),
+        });
+    }
+    Ok(())
+}
+
+/// The values one step before the start and one step after the end of
+/// `data`, as `(front, back)`.
+///
+/// The recursion depth equals the number of differencing levels, and the
+/// differences of non-polynomial data grow exponentially with the depth.
+/// Thus, fixed-size integers overflow long before the recursion could exhaust
+/// the stack.
+pub fn extrapolate_recursive<T: SeqInt>(data: &[T], stop: StopCondition) -> AOCResult<(T, T)> {
+    extrapolate_recursive_from(data, None, stop)
+}
+
+fn extrapolate_recursive_from<T: SeqInt>(
+    data: &[T],
+    parent: Option<&[T]>,
+    stop: StopCondition,
+) -> AOCResult<(T, T)> {
+    if stop.is_base(data) {
+        check_converged(data, parent)?;
+        let diff = data[0];
+        Ok((diff, diff))
+    } else {
+        let (diff_front, diff_back) = extrapolate_recursive_from(&differences(data), Some(data), stop)?;
+        let front = *data.first().unwrap() - diff_front;
+        let back = *data.last().unwrap() + diff_back;
+        Ok((front, back))
+    }
+}
+
+/// Same as `extrapolate_recursive`, but keeping only the first and last value
+/// of each level.
+pub fn extrapolate_iterative<T: SeqInt>(data: &[T], stop: StopCondition) -> AOCResult<(T, T)> {
+    let mut firsts = Vec::new();
+    let mut lasts = Vec::new();
+    let mut row = data.to_vec();
+    let mut parent = None;
+
+    while !stop.is_base(&row) {
+        firsts.push(*row.first().unwrap());
+        lasts.push(*row.last().unwrap());
+        let next = differences(&row);
+        parent = Some(std::mem::replace(&mut row, next));
+    }
+
+    check_converged(&row, parent.as_deref())?;
+    let diff = row[0];
+    let front = firsts.iter().rev().fold(diff, |diff, first| *first - diff);
+    let back = lasts.iter().rev().fold(diff, |diff, last| *last + diff);
+    Ok((front, back))
+}
+
+/// The number of differencing steps until reaching the base row, i.e. 0 for
+/// data that already satisfies `stop`.
+pub fn levels_to_base<T: SeqInt>(data: &[T], stop: StopCondition) -> AOCResult<usize> {
+    let mut row = data.to_vec();
+    let mut parent = None;
+    let mut levels = 0;
+    while !stop.is_base(&row) {
+        let next = differences(&row);
+        parent = Some(std::mem::replace(&mut row, next));
+        levels += 1;
+    }
+
+    check_converged(&row, parent.as_deref())?;
+    Ok(levels)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn differences() {
+        assert_eq!(super::differences(&[1, 3, 6, 10]), vec![2, 3, 4]);
+        assert_eq!(super::differences(&[5i8]), vec![]);
+        assert_eq!(super::differences::<u32>(&[]), vec![]);
+    }
+
+    #[test]
+    fn stop_conditions_agree() -> AOCResult<()> {
+        // Second-to-last row is constant but nonzero
+        let x = [1, 3, 7, 13, 21];
+        assert_eq!(extrapolate_iterative(&x, StopCondition::AllEqual)?, (1, 31));
+        assert_eq!(extrapolate_iterative(&x, StopCondition::AllZero)?, (1, 31));
+        assert_eq!(extrapolate_recursive(&x, StopCondition::AllEqual)?, (1, 31));
+        assert_eq!(extrapolate_recursive(&x, StopCondition::AllZero)?, (1, 31));
+
+        Ok(())
+    }
+
+    #[test]
+    fn short_linear_all_zero() -> AOCResult<()> {
+        // Both end at a single zero, the difference of a constant row
+        for stop in [StopCondition::AllEqual, StopCondition::AllZero] {
+            assert_eq!(extrapolate_iterative(&[1, 2, 3], stop)?, (0, 4));
+            assert_eq!(extrapolate_recursive(&[1, 2, 3], stop)?, (0, 4));
+            assert_eq!(extrapolate_iterative(&[5, 5], stop)?, (5, 5));
+            assert_eq!(extrapolate_recursive(&[5, 5], stop)?, (5, 5));
+            assert_eq!(extrapolate_iterative(&[3, 1, -1], stop)?, (5, -3));
+            assert_eq!(extrapolate_recursive(&[3, 1, -1], stop)?, (5, -3));
+        }
+        assert_eq!(super::levels_to_base(&[1, 2, 3], StopCondition::AllZero)?, 2);
+        assert_eq!(super::levels_to_base(&[5, 5], StopCondition::AllZero)?, 1);
+
+        // A single value can't be extrapolated, whether it's zero or not
+        for x in [[0], [7]] {
+            assert!(matches!(
+                extrapolate_iterative(&x, StopCondition::AllZero),
+                Err(AOCError::ParseError { .. })
+            ));
+            assert!(matches!(
+                extrapolate_recursive(&x, StopCondition::AllZero),
+                Err(AOCError::ParseError { .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn integer_types() -> AOCResult<()> {
+        let x: [i32; 4] = [10, 8, 6, 4];
+        assert_eq!(extrapolate_iterative(&x, StopCondition::default())?, (12, 2));
+
+        // Increasing, such that unsigned differences don't underflow
+        let x: [u64; 4] = [2, 5, 10, 17];
+        assert_eq!(extrapolate_recursive(&x, StopCondition::default())?, (1, 26));
+
+        let x: [i128; 3] = [i64::MAX as i128, i64::MAX as i128 + 1, i64::MAX as i128 + 2];
+        assert_eq!(
+            extrapolate_iterative(&x, StopCondition::default())?,
+            (i64::MAX as i128 - 1, i64::MAX as i128 + 3)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn levels_to_base() -> AOCResult<()> {
+        assert_eq!(super::levels_to_base(&[5, 5, 5], StopCondition::AllEqual)?, 0);
+        assert_eq!(super::levels_to_base(&[5, 5, 5], StopCondition::AllZero)?, 1);
+        assert_eq!(super::levels_to_base(&[0, 0], StopCondition::AllZero)?, 0);
+        assert_eq!(super::levels_to_base(&[1, 3, 7, 13, 21], StopCondition::AllEqual)?, 2);
+
+        let x: Vec<i64> = (0..20).map(|i| i * i * i).collect();
+        assert_eq!(super::levels_to_base(&x, StopCondition::AllEqual)?, 3);
+
+        assert!(matches!(
+            super::levels_to_base(&[1, 2, 4, 8], StopCondition::AllEqual),
+            Err(AOCError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn not_stabilizing() {
+        // The differences of an exponential are exponential again
+        for x in [&[1, 2, 4, 8, 16][..], &[3, -1, 4], &[7], &[]] {
+            for stop in [StopCondition::AllEqual, StopCondition::AllZero] {
+                assert!(
+                    matches!(extrapolate_iterative(x, stop), Err(AOCError::ParseError { .. })),
+                    "{:?}",
+                    x
+                );
+                assert!(
+                    matches!(extrapolate_recursive(x, stop), Err(AOCError::ParseError { .. })),
+                    "{:?}",
+                    x
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn recursive_and_iterative_agree() -> AOCResult<()> {
+        // Deep differencing (a cubic polynomial needs three levels)
+        let x: Vec<i64> = (0..1000).map(|i| i * i * i - 7 * i).collect();
+        for stop in [StopCondition::AllEqual, StopCondition::AllZero] {
+            assert_eq!(
+                extrapolate_iterative(&x, stop)?,
+                (-1 + 7, 1000 * 1000 * 1000 - 7000)
+            );
+            assert_eq!(extrapolate_recursive(&x, stop)?, extrapolate_iterative(&x, stop)?);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{AOCError, AOCResult};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Run `solve` on a separate thread, giving up after `timeout`.
+///
+/// There's no way to cancel a thread, so when timing out, the computation
+/// keeps running in the background until it finishes (or the process exits).
+pub fn solve_with_timeout<T, F>(timeout: Duration, solve: F) -> AOCResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> AOCResult<T> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        // The receiver is gone if we timed out, nothing to do about that
+        let _ = sender.send(solve());
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(AOCError::Timeout { after: timeout }),
+        // The sender was dropped without sending anything, i.e. `solve`
+        // panicked: propagate that
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => unreachable!(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finishes_in_time() -> AOCResult<()> {
+        assert_eq!(solve_with_timeout(Duration::from_secs(10), || Ok(42))?, 42);
+
+        assert!(matches!(
+            solve_with_timeout::<(), _>(Duration::from_secs(10), || Err(AOCError::NotYetSolved)),
+            Err(AOCError::NotYetSolved)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn times_out() {
+        let result = solve_with_timeout(Duration::from_millis(10), || {
+            thread::sleep(Duration::from_secs(1));
+            Ok(42)
+        });
+        assert!(matches!(result, Err(AOCError::Timeout { .. })));
+    }
+
+    #[test]
+    #[should_panic(expected = "oops")]
+    fn propagates_panics() {
+        let _ = solve_with_timeout::<(), _>(Duration::from_secs(10), || panic!("oops"));
+    }
+}
@@ -0,0 +1,10 @@
+use tracing_subscriber::EnvFilter;
+
+/// Log `tracing` events to stderr, filtered by `RUST_LOG` (e.g.
+/// `RUST_LOG=day05=debug`).
+pub fn init_tracing() {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+}
@@ -0,0 +1,22 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day01::{extractors, synthetic_input};
+
+fn extract(c: &mut Criterion) {
+    let input = synthetic_input(10_000, 1);
+
+    let mut group = c.benchmark_group("extract");
+    for (name, extractor) in extractors() {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                input
+                    .lines()
+                    .map(|l| extractor.extract(black_box(l)).unwrap())
+                    .fold(0u64, |acc, (first, last)| acc + (first * 10 + last) as u64)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, extract);
+criterion_main!(benches);
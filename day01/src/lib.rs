@@ -0,0 +1,592 @@
+use aho_corasick::AhoCorasick;
+use aoc_common::{AOCError, AOCResult, load_input};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Clone, Debug)]
+pub struct Data1 {
+    items: Vec<(u8, Option<u8>)>,
+}
+
+impl FromStr for Data1 {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let items: AOCResult<Vec<_>> = input
+            .lines()
+            .map(|l| {
+                let mut it = l.chars();
+
+                let first = it
+                    .find(|c| c.is_ascii_digit())
+                    .ok_or(AOCError::ParseError {
+                        msg: "No digit in input line".into(),
+                    })?;
+
+                let last = it.rfind(|c| c.is_ascii_digit());
+
+                Ok((
+                    first.to_digit(10).unwrap() as u8,
+                    last.map(|c| c.to_digit(10).unwrap() as u8),
+                ))
+            })
+            .collect();
+
+        Ok(Data1 { items: items? })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Data2 {
+    items: Vec<(u8, u8)>,
+}
+
+// Digits 0 to 9, either as such or spelled out. The puzzle input never spells
+// out "zero", but some variants do, so it's supported as well.
+pub fn parse_digit(s: &str) -> AOCResult<u8> {
+    let digit = match s {
+        "zero" => 0,
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        digit => digit.chars().next().unwrap().to_digit(10).unwrap() as u8,
+    };
+
+    Ok(digit)
+}
+
+const DIGIT_WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub trait DigitExtractor {
+    // Find the first and last digit in a line
+    fn extract(&self, line: &str) -> AOCResult<(u8, u8)>;
+}
+
+pub struct RegexExtractor {
+    re: Regex,
+    re_rev: Regex,
+}
+
+impl RegexExtractor {
+    pub fn new() -> Self {
+        Self {
+            re: Regex::new("([0-9]|zero|one|two|three|four|five|six|seven|eight|nine)").unwrap(),
+            re_rev: Regex::new("([0-9]|orez|eno|owt|eerht|ruof|evif|xis|neves|thgie|enin)").unwrap(),
+        }
+    }
+}
+
+impl Default for RegexExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DigitExtractor for RegexExtractor {
+    fn extract(&self, l: &str) -> AOCResult<(u8, u8)> {
+        let first = self.re.find_iter(l).next().ok_or(AOCError::ParseError {
+            msg: "No digit in input line".into(),
+        })?;
+
+        // find_iter() only returns non-overlapping matches, so we
+        // can't use the above iterator's last() to obtain the last
+        // digit, since the input can (and does) contain cases like
+        // "twone"
+        let l_rev = l.chars().rev().collect::<String>();
+        let last = self
+            .re_rev
+            .find_iter(&l_rev)
+            .next()
+            .ok_or(AOCError::ParseError {
+                msg: "No digit in input line".into(),
+            })?
+            .as_str()
+            .chars()
+            .rev()
+            .collect::<String>();
+
+        Ok((parse_digit(first.as_str())?, parse_digit(last.as_str())?))
+    }
+}
+
+// Finds all (possibly overlapping) digits and words in a single pass
+pub struct AhoCorasickExtractor {
+    ac: AhoCorasick,
+}
+
+impl AhoCorasickExtractor {
+    pub fn new() -> Self {
+        let digits = (0..10).map(|d| d.to_string());
+        let words = DIGIT_WORDS.iter().map(|w| w.to_string());
+        Self {
+            // Pattern i is the digit i, pattern 10 + i the word for it
+            ac: AhoCorasick::new(digits.chain(words)).unwrap(),
+        }
+    }
+}
+
+impl Default for AhoCorasickExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DigitExtractor for AhoCorasickExtractor {
+    fn extract(&self, l: &str) -> AOCResult<(u8, u8)> {
+        // No pattern is a prefix of another one, so there's at most one match
+        // starting at each position
+        let mut matches = self.ac.find_overlapping_iter(l);
+        let first = matches.next().ok_or(AOCError::ParseError {
+            msg: "No digit in input line".into(),
+        })?;
+        let (first, last) = matches.fold((first, first), |(first, last), m| {
+            (
+                if m.start() < first.start() { m } else { first },
+                if m.start() > last.start() { m } else { last },
+            )
+        });
+
+        let digit = |m: aho_corasick::Match| (m.pattern().as_usize() % 10) as u8;
+        Ok((digit(first), digit(last)))
+    }
+}
+
+// Check at each position whether a digit or any of the words starts there
+pub struct ScanExtractor;
+
+impl DigitExtractor for ScanExtractor {
+    fn extract(&self, l: &str) -> AOCResult<(u8, u8)> {
+        let mut digits = l.char_indices().filter_map(|(i, c)| {
+            c.to_digit(10).map(|d| d as u8).or_else(|| {
+                DIGIT_WORDS
+                    .iter()
+                    .position(|word| l[i..].starts_with(word))
+                    .map(|idx| idx as u8)
+            })
+        });
+
+        let first = digits.next().ok_or(AOCError::ParseError {
+            msg: "No digit in input line".into(),
+        })?;
+        let last = digits.next_back().unwrap_or(first);
+
+        Ok((first, last))
+    }
+}
+
+// All implementations, to compare them against each other
+pub fn extractors() -> Vec<(&'static str, Box<dyn DigitExtractor>)> {
+    vec![
+        ("regex", Box::new(RegexExtractor::new())),
+        ("aho-corasick", Box::new(AhoCorasickExtractor::new())),
+        ("scan", Box::new(ScanExtractor)),
+    ]
+}
+
+// Random lines of letters, digits and (often overlapping) digit words, for
+// benchmarking. Uses a simple xorshift generator to be reproducible without
+// pulling in a dependency.
+pub fn synthetic_input(num_lines: usize, seed: u64) -> String {
+    const PIECES: [&str; 16] = [
+        "a", "x", "qz", "e", "7", "0", "oneight", "twone", "eighthree", "sevenine", "nineight",
+        "fiv", "six", "zerone", "three", "fourfive",
+    ];
+
+    let mut state = seed | 1;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    };
+
+    let mut input = String::new();
+    for _ in 0..num_lines {
+        let len = 5 + next() % 20;
+        for _ in 0..len {
+            input.push_str(PIECES[next() % PIECES.len()]);
+        }
+        // Ensure that every line contains a digit
+        input.push(char::from(b'0' + (next() % 10) as u8));
+        input.push('\n');
+    }
+    input
+}
+
+impl Data2 {
+    // Parse with a specific extractor, `from_str` uses the Aho-Corasick one
+    pub fn from_str_with(input: &str, extractor: &dyn DigitExtractor) -> AOCResult<Self> {
+        let items: AOCResult<Vec<_>> = input.lines().map(|l| extractor.extract(l)).collect();
+
+        Ok(Data2 { items: items? })
+    }
+}
+
+impl FromStr for Data2 {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        // Not `ScanExtractor`, such that `part2_reference` (which scans the
+        // same way) remains an independent cross-check
+        Data2::from_str_with(input, &AhoCorasickExtractor::new())
+    }
+}
+
+pub fn line_values1(data: &Data1) -> Vec<u64> {
+    data.items
+        .iter()
+        .copied()
+        .map(|(first, last)| {
+            (match last {
+                Some(last) => first * 10 + last,
+                None => 11 * first,
+            }) as u64
+        })
+        .collect()
+}
+
+pub fn line_values2(data: &Data2) -> Vec<u64> {
+    data.items
+        .iter()
+        .copied()
+        .map(|(first, last)| (first * 10 + last) as u64)
+        .collect()
+}
+
+pub fn part1(data: &Data1) -> AOCResult<u64> {
+    Ok(line_values1(data).iter().sum())
+}
+
+pub fn part2(data: &Data2) -> AOCResult<u64> {
+    Ok(line_values2(data).iter().sum())
+}
+
+// Straightforward solution for part 2 to validate the extractors against:
+// Check at each position whether a digit or any of the words starts there.
+pub fn part2_reference(input: &str) -> AOCResult<u64> {
+    input
+        .lines()
+        .map(|l| {
+            let digits: Vec<u8> = l
+                .char_indices()
+                .filter_map(|(i, c)| {
+                    c.to_digit(10).map(|d| d as u8).or_else(|| {
+                        DIGIT_WORDS
+                            .iter()
+                            .position(|word| l[i..].starts_with(word))
+                            .map(|idx| idx as u8)
+                    })
+                })
+                .collect();
+
+            match (digits.first(), digits.last()) {
+                (Some(first), Some(last)) => Ok((first * 10 + last) as u64),
+                _ => Err(AOCError::ParseError {
+                    msg: "No digit in input line".into(),
+                }),
+            }
+        })
+        .sum()
+}
+
+// Part 2's values can differ arbitrarily from part 1's, but the digits that
+// part 1 finds must still be found: On the remainder of a line starting at
+// its first digit, the extractor needs to yield that digit first, and
+// likewise for the last digit.
+pub fn consistency_check_with(input: &str, extractor: &dyn DigitExtractor) -> AOCResult<()> {
+    for (i, l) in input.lines().enumerate() {
+        let (Some(first), Some(last)) = (
+            l.find(|c: char| c.is_ascii_digit()),
+            l.rfind(|c: char| c.is_ascii_digit()),
+        ) else {
+            continue;
+        };
+        let digit = |idx: usize| l.as_bytes()[idx] - b'0';
+
+        let found = (extractor.extract(&l[first..])?.0, extractor.extract(&l[..=last])?.1);
+        if found != (digit(first), digit(last)) {
+            return Err(AOCError::ParseError {
+                msg: format!(
+                    "extractor found {:?} instead of {:?} on line {}: '{}'",
+                    found,
+                    (digit(first), digit(last)),
+                    i + 1,
+                    l,
+                ).into(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn consistency_check(input: &str) -> AOCResult<()> {
+    consistency_check_with(input, &AhoCorasickExtractor::new())
+}
+
+// Both parts for a single input file
+pub fn solve_file(path: &Path) -> AOCResult<(u64, u64)> {
+    let input = load_input(path)?;
+
+    Ok((part1(&input.parse()?)?, part2(&input.parse()?)?))
+}
+
+pub type FileResult = (PathBuf, AOCResult<(u64, u64)>);
+
+// Both parts for every `*.txt` file in `dir`, sorted by path. Failures are
+// reported per file, such that one bad input doesn't abort the whole batch.
+pub fn solve_dir(dir: &Path) -> AOCResult<Vec<FileResult>> {
+    let io_error = |source| AOCError::IOError {
+        source,
+        path: Some(dir.into()),
+    };
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort_unstable();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let result = solve_file(&path);
+            (path, result)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn part1() -> AOCResult<()> {
+        let data = load_input("data/test1.txt")?.parse::<Data1>()?;
+
+        match super::part1(&data) {
+            Err(AOCError::NotYetSolved) => {}
+            Ok(result) => assert_eq!(result, 142),
+            Err(e) => return Err(e),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> AOCResult<()> {
+        let data = load_input("data/test2.txt")?.parse::<Data2>()?;
+
+        match super::part2(&data) {
+            Err(AOCError::NotYetSolved) => {}
+            Ok(result) => assert_eq!(result, 281),
+            Err(e) => return Err(e),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_reference() -> AOCResult<()> {
+        let input = load_input("data/test2.txt")?;
+        assert_eq!(super::part2_reference(&input)?, 281);
+        assert_eq!(super::part2_reference(&input)?, super::part2(&input.parse()?)?);
+        for (name, extractor) in extractors() {
+            let data = Data2::from_str_with(&input, extractor.as_ref())?;
+            assert_eq!(super::part2(&data)?, 281, "{}", name);
+        }
+
+        for (line, expected) in [
+            ("oneight", 18),
+            ("sevenine", 79),
+            ("twone", 21),
+            ("eighthree", 83),
+            ("nineight7oneightwo", 92),
+            ("5", 55),
+            ("xtwox", 22),
+        ] {
+            assert_eq!(super::part2_reference(line)?, expected, "{}", line);
+            assert_eq!(super::part2(&line.parse()?)?, expected, "{}", line);
+        }
+
+        assert!(matches!(
+            super::part2_reference("abc"),
+            Err(AOCError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn zero() -> AOCResult<()> {
+        // A leading 0 as the only digit, or with another one
+        let data = Data1::from_str("a0b\n0x5\n7y0\nzero3\n")?;
+        assert_eq!(line_values1(&data), vec![0, 5, 70, 33]);
+
+        let input = "a0b\n0x5\nzero3\n7zero\nzerone\ntwozero\n";
+        let data = Data2::from_str(input)?;
+        assert_eq!(line_values2(&data), vec![0, 5, 3, 70, 1, 20]);
+        assert_eq!(super::part2_reference(input)?, 99);
+
+        Ok(())
+    }
+
+    #[test]
+    fn line_values() -> AOCResult<()> {
+        let data = load_input("data/test1.txt")?;
+        assert_eq!(line_values1(&data.parse()?), vec![12, 38, 15, 77]);
+
+        let data = load_input("data/test2.txt")?;
+        assert_eq!(
+            line_values2(&data.parse()?),
+            vec![29, 83, 13, 24, 42, 14, 76]
+        );
+
+        Ok(())
+    }
+
+    // Only understands "one", which suffices for test1.txt
+    struct OneExtractor;
+
+    impl DigitExtractor for OneExtractor {
+        fn extract(&self, line: &str) -> AOCResult<(u8, u8)> {
+            let digits: Vec<u8> = (0..line.len())
+                .filter_map(|i| {
+                    let rest = &line[i..];
+                    if rest.starts_with("one") {
+                        Some(1)
+                    } else {
+                        rest.chars().next().unwrap().to_digit(10).map(|d| d as u8)
+                    }
+                })
+                .collect();
+
+            match (digits.first(), digits.last()) {
+                (Some(first), Some(last)) => Ok((*first, *last)),
+                _ => Err(AOCError::ParseError {
+                    msg: "No digit in input line".into(),
+                }),
+            }
+        }
+    }
+
+    #[test]
+    fn extractors_agree() -> AOCResult<()> {
+        let input = synthetic_input(1000, 1);
+        let results = extractors()
+            .iter()
+            .map(|(name, extractor)| {
+                let data = Data2::from_str_with(&input, extractor.as_ref())?;
+                Ok((*name, line_values2(&data)))
+            })
+            .collect::<AOCResult<Vec<_>>>()?;
+        for (name, values) in &results[1..] {
+            assert_eq!(values, &results[0].1, "{} vs. {}", name, results[0].0);
+        }
+
+        for (name, extractor) in extractors() {
+            for (line, expected) in [("oneight", (1, 8)), ("xtwone3", (2, 3)), ("5", (5, 5))] {
+                assert_eq!(extractor.extract(line)?, expected, "{}: {}", name, line);
+            }
+            assert!(
+                matches!(extractor.extract("abc"), Err(AOCError::ParseError { .. })),
+                "{}",
+                name
+            );
+        }
+
+        Ok(())
+    }
+
+    // Forgets about the digit 7
+    struct NoSevenExtractor;
+
+    impl DigitExtractor for NoSevenExtractor {
+        fn extract(&self, line: &str) -> AOCResult<(u8, u8)> {
+            ScanExtractor.extract(&line.replace('7', "x"))
+        }
+    }
+
+    #[test]
+    fn consistency_check() -> AOCResult<()> {
+        for path in ["data/test1.txt", "data/test2.txt"] {
+            let input = load_input(path)?;
+            super::consistency_check(&input)?;
+            for (_, extractor) in extractors() {
+                consistency_check_with(&input, extractor.as_ref())?;
+            }
+        }
+        consistency_check_with(&synthetic_input(100, 2), &RegexExtractor::new())?;
+
+        // Fine as long as there's no 7 at either end
+        consistency_check_with("a1b7c2\n", &NoSevenExtractor)?;
+        match consistency_check_with("1abc2\ntwo7nine\n", &NoSevenExtractor) {
+            Err(AOCError::ParseError { msg }) => assert!(msg.contains("line 2"), "{}", msg),
+            result => panic!("{:?}", result),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn solve_dir() -> AOCResult<()> {
+        let dir = std::env::temp_dir().join(format!("day01-solve-dir-{}", std::process::id()));
+        let write = |name: &str, content: &str| {
+            fs::write(dir.join(name), content).map_err(|source| AOCError::IOError {
+                source,
+                path: Some(dir.join(name)),
+            })
+        };
+        fs::create_dir_all(&dir).map_err(|source| AOCError::IOError {
+            source,
+            path: Some(dir.clone()),
+        })?;
+        write("b.txt", "two1nine\n")?;
+        write("a.txt", "1abc2\npqr3stu8vwx\n")?;
+        write("c.txt", "abc\n")?;
+        write("notes.md", "1\n")?;
+
+        let results = super::solve_dir(&dir);
+        fs::remove_dir_all(&dir).map_err(|source| AOCError::IOError {
+            source,
+            path: Some(dir.clone()),
+        })?;
+        let results = results?;
+
+        let names: Vec<_> = results
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["a.txt", "b.txt", "c.txt"]);
+        assert_eq!(results[0].1.as_ref().ok(), Some(&(50, 50)));
+        assert_eq!(results[1].1.as_ref().ok(), Some(&(11, 29)));
+        assert!(matches!(results[2].1, Err(AOCError::ParseError { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_extractor() -> AOCResult<()> {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\nxoneightx\n";
+
+        let data = Data2::from_str_with(input, &OneExtractor)?;
+        assert_eq!(line_values2(&data), vec![12, 38, 15, 77, 11]);
+
+        let data = Data2::from_str_with(input, &RegexExtractor::new())?;
+        assert_eq!(line_values2(&data), vec![12, 38, 15, 77, 18]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,428 @@
+use aoc_common::{AOCError, AOCResult};
+use std::cmp::{Ordering, PartialOrd};
+use std::collections::HashMap;
+use std::ops::Add;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Draw {
+    red: usize,
+    green: usize,
+    blue: usize,
+}
+
+impl PartialOrd for Draw {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.red == other.red && self.green == other.green && self.blue == other.blue {
+            Some(Ordering::Equal)
+        } else if self.red <= other.red && self.green <= other.green && self.blue <= other.blue {
+            Some(Ordering::Less)
+        } else if self.red >= other.red && self.green >= other.green && self.blue >= other.blue {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl Draw {
+    fn contains_all<'a>(self, others: impl IntoIterator<Item = &'a Self>) -> bool {
+        others.into_iter().copied().all(|d| d <= self)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            red: self.red.max(other.red),
+            green: self.green.max(other.green),
+            blue: self.blue.max(other.blue),
+        }
+    }
+
+    // The largest draw contained in both
+    pub fn componentwise_min(self, other: Self) -> Self {
+        Self {
+            red: self.red.min(other.red),
+            green: self.green.min(other.green),
+            blue: self.blue.min(other.blue),
+        }
+    }
+
+    fn power(self) -> usize {
+        self.red * self.blue * self.green
+    }
+}
+
+impl Add for Draw {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            red: self.red + other.red,
+            green: self.green + other.green,
+            blue: self.blue + other.blue,
+        }
+    }
+}
+
+// Find the first draw that wouldn't fit into the bag
+pub fn first_violating_draw(bag: Draw, draws: &[Draw]) -> Option<(usize, Draw)> {
+    draws
+        .iter()
+        .copied()
+        .enumerate()
+        .find(|(_, d)| !bag.contains_all([d]))
+}
+
+// Colors that don't appear in the input are zero, i.e. `3 red` is the same as
+// `3 red, 0 green, 0 blue`.
+//
+// Both `,` and `;` separate `count color` pairs, and empty segments (e.g. from
+// a trailing separator) are skipped. A draw without any pairs is still an
+// error.
+impl FromStr for Draw {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut segments = input
+            .split([',', ';'])
+            .filter(|s| !s.trim().is_empty())
+            .peekable();
+        if segments.peek().is_none() {
+            return Err(AOCError::ParseError {
+                msg: "empty draw".into(),
+            });
+        }
+
+        let mut out = Draw::default();
+
+        for s in segments {
+            let parse_error = || AOCError::ParseError {
+                msg: format!("invalid draw: '{}'", s.trim()).into(),
+            };
+            let (count, color) = s.trim().split_once(' ').ok_or_else(parse_error)?;
+            let count = count.trim().parse::<usize>().map_err(|_| parse_error())?;
+            match color.trim() {
+                "red" => out.red += count,
+                "green" => out.green += count,
+                "blue" => out.blue += count,
+                _ => {
+                    return Err(AOCError::ParseError {
+                        msg: "unknown color".into(),
+                    })
+                }
+            };
+        }
+
+        Ok(out)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Data {
+    games: HashMap<usize, Vec<Draw>>,
+}
+
+impl FromStr for Data {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let games = input
+            .lines()
+            .map(|l| {
+                let parse_error = || AOCError::ParseError {
+                    msg: format!("invalid game: '{}'", l).into(),
+                };
+                let (id, draws) = l.split_once(':').ok_or_else(parse_error)?;
+                let id = id
+                    .trim()
+                    .strip_prefix("Game")
+                    .ok_or_else(parse_error)?
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| parse_error())?;
+                let draws = draws
+                    .split(';')
+                    .filter(|d| !d.trim().is_empty())
+                    .map(Draw::from_str)
+                    .collect::<AOCResult<Vec<_>>>();
+                match draws {
+                    Ok(draws) if draws.is_empty() => Err(AOCError::ParseError {
+                        msg: format!("game {} has no draws", id).into(),
+                    }),
+                    Ok(draws) => Ok((id, draws)),
+                    Err(e) => Err(e),
+                }
+            })
+            .collect::<AOCResult<_>>()?;
+
+        Ok(Data { games })
+    }
+}
+
+impl Data {
+    // The games with their draws, in ascending order of their ids
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (usize, &[Draw])> {
+        let mut ids: Vec<_> = self.games.keys().copied().collect();
+        ids.sort_unstable();
+        ids.into_iter().map(|id| (id, &self.games[&id][..]))
+    }
+}
+
+pub const BAG: Draw = Draw {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+// Sorted ids of the games that are possible with the given bag
+pub fn possible_games(data: &Data, bag: Draw) -> Vec<usize> {
+    data.iter_sorted()
+        .filter(|(_, draws)| bag.contains_all(*draws))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+pub fn part1(data: &Data) -> AOCResult<usize> {
+    Ok(possible_games(data, BAG).iter().sum())
+}
+
+pub fn report_part1(data: &Data) {
+    for (id, draws) in data.iter_sorted() {
+        if let Some((idx, draw)) = first_violating_draw(BAG, draws) {
+            println!("Game {} is impossible due to draw {}: {:?}", id, idx + 1, draw);
+        }
+    }
+}
+
+pub fn part2(data: &Data) -> AOCResult<usize> {
+    let total = data
+        .games
+        .values()
+        .map(|draws| draws.iter().copied().reduce(Draw::union).unwrap())
+        .map(Draw::power)
+        .sum();
+
+    Ok(total)
+}
+
+// The smallest bag that would have made every game possible
+pub fn minimal_bag(data: &Data) -> Draw {
+    data.games
+        .values()
+        .flatten()
+        .copied()
+        .fold(Draw::default(), Draw::union)
+}
+
+// All cubes drawn over all games, and the component-wise maximum of all draws
+pub fn stats(data: &Data) -> (Draw, Draw) {
+    let total = data
+        .games
+        .values()
+        .flatten()
+        .copied()
+        .fold(Draw::default(), Draw::add);
+
+    (total, minimal_bag(data))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::FromFile;
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $dtype:ty,
+            $compute:path,
+            $expected:literal
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                match $compute(&<$dtype>::from_file($datapath)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(part1, "data/test1.txt", Data, super::part1, 8);
+    aoc_test!(part2, "data/test1.txt", Data, super::part2, 2286);
+
+    #[test]
+    fn iter_sorted() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        let games: Vec<_> = data.iter_sorted().map(|(id, draws)| (id, draws.len())).collect();
+        assert_eq!(games, vec![(1, 3), (2, 3), (3, 3), (4, 3), (5, 2)]);
+
+        let data = Data::from_str("Game 10: 1 red\nGame 2: 2 red\nGame 7: 3 red; 4 red\n")?;
+        let ids: Vec<_> = data.iter_sorted().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![2, 7, 10]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn possible_games() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::possible_games(&data, BAG), vec![1, 2, 5]);
+        assert_eq!(super::possible_games(&data, Draw::default()), vec![]);
+        assert_eq!(super::possible_games(&data, super::minimal_bag(&data)), vec![1, 2, 3, 4, 5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn minimal_bag() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        let bag = super::minimal_bag(&data);
+        assert_eq!(bag, Draw { red: 20, green: 13, blue: 15 });
+        assert!(data.games.values().all(|draws| bag.contains_all(draws)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn stats() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(
+            super::stats(&data),
+            (
+                Draw { red: 61, green: 48, blue: 50 },
+                Draw { red: 20, green: 13, blue: 15 },
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_order() {
+        let a = Draw { red: 3, green: 1, blue: 0 };
+        let b = Draw { red: 1, green: 1, blue: 2 };
+        let c = Draw { red: 3, green: 2, blue: 0 };
+
+        assert_eq!(a.partial_cmp(&a), Some(Ordering::Equal));
+        assert_eq!(a.partial_cmp(&c), Some(Ordering::Less));
+        assert_eq!(c.partial_cmp(&a), Some(Ordering::Greater));
+
+        // More red, but less blue
+        assert_eq!(a.partial_cmp(&b), None);
+        assert_eq!(b.partial_cmp(&a), None);
+        assert!(!a.le(&b) && !b.le(&a) && !a.ge(&b));
+        assert!(!a.contains_all([&b]) && !b.contains_all([&a]));
+    }
+
+    #[test]
+    fn min_max() {
+        let a = Draw { red: 3, green: 1, blue: 0 };
+        let b = Draw { red: 1, green: 1, blue: 2 };
+
+        let max = a.union(b);
+        let min = a.componentwise_min(b);
+        assert_eq!(max, Draw { red: 3, green: 1, blue: 2 });
+        assert_eq!(min, Draw { red: 1, green: 1, blue: 0 });
+        assert_eq!(b.union(a), max);
+        assert_eq!(b.componentwise_min(a), min);
+
+        // Bounds of both, and the identity for comparable draws
+        assert!(min <= a && min <= b && a <= max && b <= max);
+        assert_eq!(min.union(max), max);
+        assert_eq!(min.componentwise_min(max), min);
+        assert_eq!(a.componentwise_min(Draw::default()), Draw::default());
+    }
+
+    #[test]
+    fn partial_draws() -> AOCResult<()> {
+        assert_eq!(Draw::from_str("3 red")?, Draw { red: 3, green: 0, blue: 0 });
+        assert_eq!(Draw::from_str(" 5 blue ")?, Draw { red: 0, green: 0, blue: 5 });
+        assert_eq!(Draw::from_str("0 green")?, Draw::default());
+
+        let bag = Draw::from_str("2 green")?;
+        assert!(bag.contains_all(&[Draw::from_str("1 green")?, Draw::from_str("0 red")?]));
+        assert!(!bag.contains_all(&[Draw::from_str("1 red")?]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn separators() -> AOCResult<()> {
+        let expected = Draw { red: 3, green: 0, blue: 5 };
+        for input in [
+            "3 red, 5 blue,",
+            "3 red, 5 blue;",
+            "3 red; 5 blue",
+            " 3 red ,, 5 blue , ",
+            ",3 red,  5  blue",
+        ] {
+            assert_eq!(Draw::from_str(input)?, expected, "{:?}", input);
+        }
+
+        let data = Data::from_str("Game 2: 3 red, 5 blue; 1 green;\n")?;
+        assert_eq!(
+            data.games[&2],
+            vec![expected, Draw { red: 0, green: 1, blue: 0 }]
+        );
+
+        assert!(matches!(
+            Data::from_str("Game 3: ;\n"),
+            Err(AOCError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn game_ids() -> AOCResult<()> {
+        let expected = vec![Draw { red: 3, green: 0, blue: 0 }];
+        for input in [
+            "Game 12: 3 red\n",
+            "Game  12 : 3 red\n",
+            "  Game\t12:3 red\n",
+            "Game12 :3 red \n",
+        ] {
+            let data = Data::from_str(input)?;
+            assert_eq!(data.games.get(&12), Some(&expected), "{:?}", input);
+        }
+
+        for input in ["Game 12 3 red\n", "Gme 12: 3 red\n", "Game x: 3 red\n", "Game : 3 red\n"] {
+            match Data::from_str(input) {
+                Err(AOCError::ParseError { msg }) => assert!(msg.contains(input.trim_end()), "{}", msg),
+                result => panic!("{:?}: {:?}", input, result),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_draws() {
+        for input in ["", "  ", ",", " ; , ", "3", "red", "x red", "3 purple", "3 red, blue,"] {
+            assert!(
+                matches!(Draw::from_str(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn first_violating_draw() -> AOCResult<()> {
+        let data = Data::from_str("Game 7: 3 blue, 4 red; 1 red, 20 green; 15 blue\n")?;
+        assert_eq!(
+            super::first_violating_draw(BAG, &data.games[&7]),
+            Some((1, Draw { red: 1, green: 20, blue: 0 })),
+        );
+
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::first_violating_draw(BAG, &data.games[&1]), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,431 @@
+use aoc_common::parse::read_grid;
+use aoc_common::{AOCError, AOCResult, Point};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// FIXME: Didn't really turn out to be a very useful datastructure: Due to
+// duplicating the ids in id_map, I need to constantly pay attention to dedup
+// again when doing the actual computation.
+// In principle, this code should have linear scaling (with the number of parts),
+// but it would be nicer to abstract it away into a generic data structure that
+// handles the duplication issues.
+#[derive(Clone, Debug)]
+pub struct Data {
+    // (id, is_part)
+    ids: Vec<(u64, bool)>,
+
+    // location -> entry in ids
+    id_map: HashMap<Point, usize>,
+
+    // location -> part
+    parts: HashMap<Point, char>,
+
+    // Size of the (rectangular) grid
+    width: usize,
+    height: usize,
+}
+
+impl FromStr for Data {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut ids = Vec::new();
+        let mut id_map = HashMap::new();
+        let mut parts = HashMap::new();
+
+        let mut chars = Vec::new();
+
+        let mut store_id = |x: i32, y: i32, chars: &mut Vec<char>| -> AOCResult<()> {
+            let num_digits = chars.len() as i32;
+            if num_digits == 0 {
+                return Ok(());
+            }
+            let id: String = chars.drain(..).collect();
+            // Must be an integer since we only collect 0..9 into chars, but
+            // might be too large.
+            let id = id.parse::<u64>().map_err(|_| AOCError::ParseError {
+                msg: format!("part number too large: {}", id).into(),
+            })?;
+            ids.push((id, false));
+            let idx = ids.len() - 1;
+            for offset in 1..=num_digits {
+                id_map.insert(Point::new(x - offset, y), idx);
+            }
+            Ok(())
+        };
+
+        // An empty line is the same as a line of dots, except for trailing ones
+        // which aren't part of the grid
+        let mut lines: Vec<_> = input.lines().collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let input: String = lines
+            .into_iter()
+            .map(|l| if l.is_empty() { ".".repeat(width) } else { l.to_owned() } + "\n")
+            .collect();
+        let grid = read_grid(&input)?;
+        let width = grid.first().map_or(0, Vec::len);
+        let height = grid.len();
+
+        for (y, l) in (0i32..).zip(grid) {
+            let mut it = (0i32..).zip(l).peekable();
+            while let Some((x, c)) = it.next() {
+                match c {
+                    '.' => {},
+                    '0'..='9' => {
+                        chars.push(c);
+                        // If the line ends here, the number also necessarily
+                        // ends, and it includes the current position
+                        if it.peek().is_none() {
+                            store_id(x + 1, y, &mut chars)?;
+                        }
+                        continue;
+                    },
+                    _ => { parts.insert(Point::new(x, y), c); }
+                }
+
+                // A number ended, parse and store it
+                store_id(x, y, &mut chars)?;
+            }
+        }
+
+        Ok(Data { ids, id_map, parts, width, height })
+    }
+}
+
+// A symbol and its (x, y) location
+pub type Symbol = (char, (i32, i32));
+
+impl Data {
+    // (width, height) of the grid
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    // Entries in ids adjacent to the given location, without duplicates
+    fn adjacent_ids(&self, loc: Point) -> Vec<usize> {
+        let mut idxs: Vec<_> = loc
+            .neighbors8()
+            .filter_map(|neighbor| self.id_map.get(&neighbor).copied())
+            .collect();
+        idxs.sort_unstable();
+        idxs.dedup();
+        idxs
+    }
+
+    // Numbers adjacent to the given location. Each number appears once per
+    // run of digits, i.e. two distinct runs with the same value are both
+    // included.
+    pub fn numbers_adjacent_to(&self, x: i32, y: i32) -> Vec<u64> {
+        self.adjacent_ids(Point::new(x, y))
+            .into_iter()
+            .map(|idx| self.ids[idx].0)
+            .collect()
+    }
+
+    // Each run of digits in reading order, with the symbols adjacent to it
+    // (sorted by location, row-major)
+    pub fn adjacent_symbols(&self) -> Vec<(u64, Vec<Symbol>)> {
+        let mut symbols = vec![Vec::new(); self.ids.len()];
+        for (loc, c) in &self.parts {
+            for idx in self.adjacent_ids(*loc) {
+                symbols[idx].push((*c, (loc.x, loc.y)));
+            }
+        }
+
+        self.ids.iter()
+            .zip(symbols)
+            .map(|((id, _), mut symbols)| {
+                symbols.sort_unstable_by_key(|(_, (x, y))| (*y, *x));
+                (*id, symbols)
+            })
+            .collect()
+    }
+
+    // Set is_part for all numbers adjacent to any symbol
+    fn mark_parts(&mut self) {
+        let locs: Vec<_> = self.parts.keys().copied().collect();
+        for loc in locs {
+            for idx in self.adjacent_ids(loc) {
+                self.ids[idx].1 = true;
+            }
+        }
+    }
+}
+
+fn checked_sum(values: impl IntoIterator<Item = u64>) -> AOCResult<u64> {
+    values
+        .into_iter()
+        .try_fold(0u64, |acc, value| acc.checked_add(value))
+        .ok_or(AOCError::Overflow)
+}
+
+pub fn part1(data: &mut Data) -> AOCResult<u64> {
+    data.mark_parts();
+
+    checked_sum(data.ids.iter().copied()
+        .map(|(id, is_part)| { if is_part { id } else { 0 } })
+    )
+}
+
+// Sum of all numbers, whether they're part numbers or not
+pub fn sum_all_numbers(data: &Data) -> AOCResult<u64> {
+    checked_sum(data.ids.iter().map(|(id, _)| *id))
+}
+
+// Numbers that aren't adjacent to any symbol, in reading order
+pub fn non_part_numbers(data: &mut Data) -> Vec<u64> {
+    data.mark_parts();
+
+    data.ids.iter().copied()
+        .filter_map(|(id, is_part)| if is_part { None } else { Some(id) })
+        .collect()
+}
+
+// Each part number with the number of symbols adjacent to it
+pub fn part_adjacencies(data: &Data) -> Vec<(u64, usize)> {
+    data.adjacent_symbols()
+        .into_iter()
+        .filter(|(_, symbols)| !symbols.is_empty())
+        .map(|(id, symbols)| (id, symbols.len()))
+        .collect()
+}
+
+pub fn part2(data: &Data) -> AOCResult<u64> {
+    let ratios = data.parts.iter()
+        .filter_map(|(loc, c)| if *c == '*' { Some(loc) } else { None })
+        .map(|loc| {
+            match data.numbers_adjacent_to(loc.x, loc.y)[..] {
+                [id1, id2] => id1.checked_mul(id2).ok_or(AOCError::Overflow),
+                _ => Ok(0),
+            }
+        })
+        .collect::<AOCResult<Vec<_>>>()?;
+
+    checked_sum(ratios)
+}
+
+// Both parts in a single pass over the symbols, sharing the neighbor lookups
+pub fn solve_both(data: &mut Data) -> AOCResult<(u64, u64)> {
+    let mut gear_ratios = 0u64;
+    let locs: Vec<_> = data.parts.iter().map(|(loc, c)| (*loc, *c)).collect();
+    for (loc, c) in locs {
+        let idxs = data.adjacent_ids(loc);
+        for idx in &idxs {
+            data.ids[*idx].1 = true;
+        }
+        if let (&[idx1, idx2], '*') = (&idxs[..], c) {
+            gear_ratios = data.ids[idx1].0
+                .checked_mul(data.ids[idx2].0)
+                .and_then(|ratio| gear_ratios.checked_add(ratio))
+                .ok_or(AOCError::Overflow)?;
+        }
+    }
+
+    let part_numbers = checked_sum(data.ids.iter().copied()
+        .map(|(id, is_part)| { if is_part { id } else { 0 } })
+    )?;
+
+    Ok((part_numbers, gear_ratios))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::FromFile;
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $dtype:ty,
+            $compute:path,
+            $expected:expr
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                match $compute(&mut <$dtype>::from_file($datapath)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(part1, "data/test1.txt", Data, super::part1, 4361);
+    aoc_test!(part2, "data/test1.txt", Data, super::part2, 467835);
+    aoc_test!(solve_both, "data/test1.txt", Data, super::solve_both, (4361, 467835));
+
+    #[test]
+    fn dimensions() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(data.dimensions(), (10, 10));
+
+        assert_eq!(Data::from_str("12*12\n")?.dimensions(), (5, 1));
+        assert_eq!(Data::from_str("")?.dimensions(), (0, 0));
+        assert!(matches!(Data::from_str("12*\n1\n"), Err(AOCError::ParseError { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_part_numbers() -> AOCResult<()> {
+        let mut data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::non_part_numbers(&mut data), vec![114, 58]);
+        assert_eq!(super::part1(&mut data)?, 4361);
+
+        let mut data = Data::from_str("1.2\n...\n")?;
+        assert_eq!(super::non_part_numbers(&mut data), vec![1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sum_all_numbers() -> AOCResult<()> {
+        let mut data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::sum_all_numbers(&data)?, 4533);
+        // Doesn't depend on marking the parts
+        let non_parts: u64 = super::non_part_numbers(&mut data).iter().sum();
+        assert_eq!(super::sum_all_numbers(&data)?, super::part1(&mut data)? + non_parts);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_adjacencies() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        let adjacencies = super::part_adjacencies(&data);
+        assert_eq!(adjacencies.len(), 8);
+        assert!(adjacencies.iter().all(|(_, count)| *count == 1));
+
+        let data = Data::from_str("..*..\n.12.3\n...#.\n")?;
+        assert_eq!(super::part_adjacencies(&data), vec![(12, 2), (3, 1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn adjacent_symbols() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        let symbols = data.adjacent_symbols();
+        assert_eq!(symbols.len(), 10);
+        assert_eq!(symbols[0], (467, vec![('*', (3, 1))]));
+        assert_eq!(symbols[1], (114, vec![]));
+        assert_eq!(symbols[3], (633, vec![('#', (6, 3))]));
+
+        let data = Data::from_str("..*..\n.12.3\n...#.\n")?;
+        assert_eq!(
+            data.adjacent_symbols(),
+            vec![(12, vec![('*', (2, 0)), ('#', (3, 2))]), (3, vec![('#', (3, 2))])]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn numbers_adjacent_to() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(data.numbers_adjacent_to(3, 1), vec![467, 35]);
+        assert_eq!(data.numbers_adjacent_to(5, 8), vec![755, 598]);
+        // Not a gear, only adjacent to a single number
+        assert_eq!(data.numbers_adjacent_to(3, 4), vec![617]);
+        assert_eq!(data.numbers_adjacent_to(0, 9), vec![664]);
+        assert!(data.numbers_adjacent_to(9, 0).is_empty());
+
+        // Equal numbers are still distinct
+        let data = Data::from_str("12*12\n")?;
+        assert_eq!(data.numbers_adjacent_to(2, 0), vec![12, 12]);
+        assert_eq!(super::part2(&data)?, 144);
+
+        Ok(())
+    }
+
+    #[test]
+    fn large_gear_ratio() -> AOCResult<()> {
+        // The product exceeds i32::MAX
+        let mut data = Data::from_str("50000*60000\n")?;
+        assert_eq!(super::part2(&data)?, 3_000_000_000);
+        assert_eq!(super::solve_both(&mut data)?, (110_000, 3_000_000_000));
+
+        // Doesn't fit into u64 anymore
+        let data = Data::from_str("10000000000*20000000000\n")?;
+        assert!(matches!(super::part2(&data), Err(AOCError::Overflow)));
+
+        // Each ratio fits, but not their sum
+        let mut data = Data::from_str(
+            "4294967296*4294967295\n\
+             .....................\n\
+             4294967296*4294967295\n",
+        )?;
+        assert!(matches!(super::part2(&data), Err(AOCError::Overflow)));
+        assert!(matches!(super::solve_both(&mut data), Err(AOCError::Overflow)));
+
+        // Each part number fits, but not their sum
+        let mut data = Data::from_str("18446744073709551615+1\n")?;
+        assert!(matches!(super::part1(&mut data), Err(AOCError::Overflow)));
+        assert!(matches!(super::sum_all_numbers(&data), Err(AOCError::Overflow)));
+        assert!(matches!(super::solve_both(&mut data), Err(AOCError::Overflow)));
+
+        assert!(matches!(
+            Data::from_str("100000000000000000000*1\n"),
+            Err(AOCError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn blank_lines() -> AOCResult<()> {
+        // Lines of only dots don't register any numbers
+        let data = Data::from_str("....\n....\n")?;
+        assert!(data.ids.is_empty());
+        assert!(data.id_map.is_empty());
+
+        let mut data = Data::from_str("467.\n....\n.*..\n")?;
+        assert_eq!(data.ids, vec![(467, false)]);
+        assert_eq!(super::part1(&mut data)?, 0);
+
+        // An empty line between populated lines counts as a row
+        let mut data = Data::from_str("467..\n\n.*...\n..35.\n")?;
+        assert_eq!(data.dimensions(), (5, 4));
+        assert_eq!(data.numbers_adjacent_to(1, 2), vec![35]);
+        assert_eq!(super::non_part_numbers(&mut data), vec![467]);
+        assert_eq!(super::part1(&mut data)?, 35);
+
+        // Trailing empty lines are not
+        let mut data = Data::from_str("..*..\n\n")?;
+        assert_eq!(data.dimensions(), (5, 1));
+        assert_eq!(super::part1(&mut data)?, 0);
+        let data = Data::from_str("..*..\n\n\n.....\n\n")?;
+        assert_eq!(data.dimensions(), (5, 4));
+
+        // Width counts characters, not bytes
+        let data = Data::from_str("1§..\n\n.*..\n")?;
+        assert_eq!(data.dimensions(), (4, 3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn diagonal_adjacency() -> AOCResult<()> {
+        // Symbols diagonally across a line boundary from the first and last
+        // digit of a number
+        let mut data = Data::from_str("467..\n...*.\n.*...\n..35.\n")?;
+        assert_eq!(super::part1(&mut data)?, 467 + 35);
+
+        // A number that ends at the end of its line
+        let mut data = Data::from_str("...12\n.....\n..*..\n.123.\n")?;
+        assert_eq!(super::part1(&mut data)?, 123);
+        let mut data = Data::from_str("...12\n.*...\n")?;
+        assert_eq!(super::part1(&mut data)?, 0);
+        let mut data = Data::from_str("...12\n..*..\n")?;
+        assert_eq!(super::part1(&mut data)?, 12);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,364 @@
+use aoc_common::{AOCError, AOCResult, Memo};
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Clone, Debug)]
+pub struct Card {
+    winning: HashSet<u32>,
+    yours: Vec<u32>,
+}
+
+impl Card {
+    fn num_matching(&self) -> usize {
+        self.yours
+            .iter()
+            .filter(|num| self.winning.contains(num))
+            .count()
+    }
+
+    fn score(&self) -> AOCResult<i64> {
+        self.score_with_base(2)
+    }
+
+    // base^(count - 1) for count matching numbers, 0 if there are none
+    fn score_with_base(&self, base: i64) -> AOCResult<i64> {
+        let count = self.num_matching();
+
+        match count {
+            0 => Ok(0),
+            _ => u32::try_from(count - 1)
+                .ok()
+                .and_then(|exp| base.checked_pow(exp))
+                .ok_or(AOCError::Overflow),
+        }
+    }
+}
+
+// Numbers that occur more than once, each reported once in order of their
+// second occurrence
+fn duplicate_numbers(numbers: &[u32]) -> Vec<u32> {
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    for num in numbers {
+        if !seen.insert(num) && !duplicates.contains(num) {
+            duplicates.push(*num);
+        }
+    }
+    duplicates
+}
+
+impl FromStr for Card {
+    type Err = AOCError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let parse_error = || AOCError::ParseError {
+            msg: format!("invalid card: '{}'", line).into(),
+        };
+        let (winning, yours) = line
+            .split_once(':')
+            .and_then(|(_, numbers)| numbers.split_once('|'))
+            .ok_or_else(parse_error)?;
+        let winning = winning
+            .split_ascii_whitespace()
+            .map(|w| w.parse::<u32>().map_err(|_| parse_error()))
+            .collect::<AOCResult<Vec<_>>>()?;
+        // Harmless for matching, but likely a sign of corrupt input
+        let duplicates = duplicate_numbers(&winning);
+        if !duplicates.is_empty() {
+            return Err(AOCError::ParseError {
+                msg: format!("duplicate winning numbers {:?}: '{}'", duplicates, line).into(),
+            });
+        }
+        let winning = winning.into_iter().collect();
+        let yours = yours
+            .split_ascii_whitespace()
+            .map(|w| w.parse::<u32>().map_err(|_| parse_error()))
+            .collect::<AOCResult<_>>()?;
+
+        Ok(Card { winning, yours })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Data {
+    cards: Vec<Card>,
+}
+
+impl FromStr for Data {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let cards = input
+            .lines()
+            .map(Card::from_str)
+            .collect::<AOCResult<_>>()?;
+
+        Ok(Data { cards })
+    }
+}
+
+pub fn part1(data: &Data) -> AOCResult<(i64, Vec<i64>)> {
+    let scores = data
+        .cards
+        .iter()
+        .map(Card::score)
+        .collect::<AOCResult<Vec<_>>>()?;
+
+    let total = scores
+        .iter()
+        .try_fold(0i64, |acc, &score| acc.checked_add(score))
+        .ok_or(AOCError::Overflow)?;
+
+    Ok((total, scores))
+}
+
+// The score of each card from `part1`, along with its 1-based number
+pub fn scores_labeled(data: &Data) -> AOCResult<Vec<(usize, i64)>> {
+    Ok((1..).zip(part1(data)?.1).collect())
+}
+
+// Same total as `part1`, but only holds a single card in memory at a time
+pub fn part1_streaming(input: impl BufRead) -> AOCResult<i64> {
+    let mut total = 0i64;
+
+    for line in input.lines() {
+        let line = line.map_err(|source| AOCError::IOError { source, path: None })?;
+        let score = Card::from_str(&line)?.score()?;
+        total = total.checked_add(score).ok_or(AOCError::Overflow)?;
+    }
+
+    Ok(total)
+}
+
+// 1-based number of the first card without any matching numbers
+pub fn first_losing_card(data: &Data) -> Option<usize> {
+    data.cards
+        .iter()
+        .position(|card| card.num_matching() == 0)
+        .map(|idx| idx + 1)
+}
+
+// Final number of copies of each card
+pub fn copy_counts(data: &Data) -> AOCResult<Vec<u64>> {
+    let mut count = vec![1u64; data.cards.len()];
+
+    for (i, card) in data.cards.iter().enumerate() {
+        let ci = count[i];
+        for j in (i + 1)..=(i + card.num_matching()) {
+            if let Some(cj) = count.get_mut(j) {
+                *cj = cj.checked_add(ci).ok_or(AOCError::Overflow)?;
+            }
+        }
+    }
+
+    Ok(count)
+}
+
+pub fn part2(data: &Data) -> AOCResult<i64> {
+    let total = copy_counts(data)?
+        .iter()
+        .try_fold(0u64, |acc, &count| acc.checked_add(count))
+        .ok_or(AOCError::Overflow)?;
+    i64::try_from(total).map_err(|_| AOCError::Overflow)
+}
+
+// Number of cards obtained from card `i`, including itself
+//
+// The counts can grow exponentially with the number of cards, overflows are
+// cached as `None`.
+pub fn total_cards(data: &Data, memo: &mut Memo<usize, Option<u64>>, i: usize) -> AOCResult<u64> {
+    memo.get_or_compute(i, |memo, &i| {
+        let won = (i + 1)..=(i + data.cards[i].num_matching()).min(data.cards.len() - 1);
+        won.map(|j| total_cards(data, memo, j).ok())
+            .try_fold(1u64, |acc, count| acc.checked_add(count?))
+    })
+    .ok_or(AOCError::Overflow)
+}
+
+// Recursive variant of part2, mostly useful as a cross-check
+pub fn part2_recursive(data: &Data) -> AOCResult<i64> {
+    let mut memo = Memo::new();
+
+    let total = (0..data.cards.len()).try_fold(0u64, |acc, i| {
+        acc.checked_add(total_cards(data, &mut memo, i)?)
+            .ok_or(AOCError::Overflow)
+    })?;
+    i64::try_from(total).map_err(|_| AOCError::Overflow)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::{load_input, FromFile};
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $dtype:ty,
+            $compute:path,
+            $expected:expr
+            $(,)?
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                match $compute(&<$dtype>::from_file($datapath)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(
+        part1,
+        "data/test1.txt",
+        Data,
+        super::part1,
+        (13, vec![8, 2, 2, 1, 0, 0]),
+    );
+    aoc_test!(part2, "data/test1.txt", Data, super::part2, 30);
+    aoc_test!(
+        part2_recursive,
+        "data/test1.txt",
+        Data,
+        super::part2_recursive,
+        30,
+    );
+
+    #[test]
+    fn copy_counts() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::copy_counts(&data)?, vec![1, 2, 4, 8, 14, 1]);
+
+        Ok(())
+    }
+
+    // Each card wins a copy of every following card, such that card `i` of
+    // `n` yields 2^(n - 1 - i) cards in total
+    fn exploding_deck(n: usize) -> AOCResult<Data> {
+        (0..n)
+            .map(|i| {
+                let numbers: Vec<_> = (1..n - i).map(|num| num.to_string()).collect();
+                let numbers = numbers.join(" ");
+                format!("Card {}: {} | {}\n", i + 1, numbers, numbers)
+            })
+            .collect::<String>()
+            .parse()
+    }
+
+    #[test]
+    fn total_cards() -> AOCResult<()> {
+        // Doesn't fit into u32
+        let data = exploding_deck(40)?;
+        let mut memo = Memo::new();
+        assert_eq!(super::total_cards(&data, &mut memo, 0)?, 1 << 39);
+        assert_eq!(super::part2_recursive(&data)?, (1 << 40) - 1);
+        assert_eq!(super::part2(&data)?, (1 << 40) - 1);
+
+        // Card 1 alone yields 2^64 cards
+        let data = exploding_deck(65)?;
+        let mut memo = Memo::new();
+        assert_eq!(super::total_cards(&data, &mut memo, 1)?, 1 << 63);
+        assert!(matches!(super::total_cards(&data, &mut memo, 0), Err(AOCError::Overflow)));
+        assert!(matches!(super::part2_recursive(&data), Err(AOCError::Overflow)));
+        assert!(matches!(super::copy_counts(&data), Err(AOCError::Overflow)));
+        assert!(matches!(super::part2(&data), Err(AOCError::Overflow)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn part1_streaming() -> AOCResult<()> {
+        let input = load_input("data/test1.txt")?;
+        let data = Data::from_str(&input)?;
+        assert_eq!(super::part1_streaming(input.as_bytes())?, super::part1(&data)?.0);
+        assert_eq!(super::part1_streaming(&b""[..])?, 0);
+
+        assert!(matches!(
+            super::part1_streaming(&b"Card 1: 1 2 3\n"[..]),
+            Err(AOCError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_winning_numbers() -> AOCResult<()> {
+        assert_eq!(duplicate_numbers(&[41, 48, 83, 86, 17]), vec![]);
+        assert_eq!(duplicate_numbers(&[41, 48, 41, 86, 48, 41]), vec![41, 48]);
+
+        match Card::from_str("Card 1: 41 48 41 | 41 48 83 41") {
+            Err(AOCError::ParseError { msg }) => assert!(msg.starts_with("duplicate winning numbers [41]"), "{}", msg),
+            result => panic!("{:?}", result),
+        }
+
+        // Duplicates among your numbers are fine, and each of them counts
+        let card = Card::from_str("Card 1: 41 48 | 41 48 83 41")?;
+        assert_eq!(card.winning, HashSet::from([41, 48]));
+        assert_eq!(card.num_matching(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scores_labeled() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(
+            super::scores_labeled(&data)?,
+            vec![(1, 8), (2, 2), (3, 2), (4, 1), (5, 0), (6, 0)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn first_losing_card() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::first_losing_card(&data), Some(5));
+
+        let data = Data::from_str("Card 1: 1 2 | 2 3\n")?;
+        assert_eq!(super::first_losing_card(&data), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn wide_numbers() -> AOCResult<()> {
+        let data = Data::from_str("Card 1: 999 256 3 | 999 1000 3 256\nCard 2: 999 | 998\n")?;
+        assert_eq!(data.cards[0].num_matching(), 3);
+        assert_eq!(data.cards[1].num_matching(), 0);
+        assert_eq!(super::part1(&data)?, (4, vec![4, 0]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn score_with_base() -> AOCResult<()> {
+        let card = Card {
+            winning: HashSet::from([1, 2, 3, 4]),
+            yours: vec![1, 2, 3, 5],
+        };
+        assert_eq!(card.score_with_base(3)?, 9);
+        assert_eq!(card.score()?, 4);
+
+        let card = Card {
+            winning: HashSet::from([1]),
+            yours: vec![2],
+        };
+        assert_eq!(card.score_with_base(3)?, 0);
+
+        // 3^64 doesn't fit into i64
+        let card = Card {
+            winning: (0..=64).collect(),
+            yours: (0..=64).collect(),
+        };
+        assert!(matches!(card.score_with_base(3), Err(AOCError::Overflow)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,814 @@
+use aoc_common::parse::split_blocks;
+use aoc_common::{AOCError, AOCResult};
+use itertools::Itertools;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use tracing::{debug, instrument, trace};
+
+#[derive(Clone, Debug)]
+pub struct MapInterval {
+    len: u64,
+    src_start: u64,
+    dest_start: u64,
+}
+
+impl FromStr for MapInterval {
+    type Err = AOCError;
+
+    // Intervals must not be empty, and may reach up to u64::MAX (inclusive)
+    // on both the source and the destination side, but not beyond
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = || AOCError::ParseError {
+            msg: format!("incorrect range: '{}'", s).into(),
+        };
+        let (dest_start, src_start, len) = s
+            .split_ascii_whitespace()
+            .map(u64::from_str)
+            .collect_tuple()
+            .ok_or_else(parse_error)?;
+        let (dest_start, src_start, len) = (
+            dest_start.map_err(|_| parse_error())?,
+            src_start.map_err(|_| parse_error())?,
+            len.map_err(|_| parse_error())?,
+        );
+        if len == 0 {
+            return Err(AOCError::ParseError {
+                msg: format!("empty range: '{}'", s).into(),
+            });
+        }
+        if src_start.checked_add(len - 1).is_none() || dest_start.checked_add(len - 1).is_none() {
+            return Err(AOCError::ParseError {
+                msg: format!("range exceeds u64::MAX: '{}'", s).into(),
+            });
+        }
+
+        Ok(Self { len, src_start, dest_start })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AMap {
+    ranges: Vec<MapInterval>,
+}
+
+impl AMap {
+    pub fn get(&self, index: u64) -> u64 {
+        for MapInterval {len, src_start, dest_start} in &self.ranges {
+            if index >= *src_start && index - *src_start < *len {
+                return dest_start.saturating_add(index - *src_start);
+            }
+        }
+
+        index
+    }
+
+    // Same as `get`, but failing rather than saturating if the destination
+    // doesn't fit. That can't happen for intervals that went through
+    // `MapInterval::from_str`, this is merely a safety net.
+    pub fn checked_get(&self, index: u64) -> AOCResult<u64> {
+        for MapInterval {len, src_start, dest_start} in &self.ranges {
+            if index >= *src_start && index - *src_start < *len {
+                return dest_start
+                    .checked_add(index - *src_start)
+                    .ok_or(AOCError::Overflow);
+            }
+        }
+
+        Ok(index)
+    }
+
+    // All indices that `get` maps to `index`, in increasing order. There may be
+    // several, since the identity mapping outside of the intervals can collide
+    // with the destination of an interval.
+    fn get_inverse(&self, index: u64) -> Vec<u64> {
+        let mut out: Vec<_> = self.ranges
+            .iter()
+            .filter(|r| index >= r.dest_start && index - r.dest_start < r.len)
+            .map(|r| r.src_start + (index - r.dest_start))
+            .collect();
+        if !self.ranges.iter().any(|r| index >= r.src_start && index - r.src_start < r.len) {
+            out.push(index);
+        }
+        out.sort_unstable();
+        out.dedup();
+
+        out
+    }
+
+    // Each iteration of `get_range_with_cap` consumes at least up to the next
+    // interval boundary, so a range is split into at most one segment per
+    // interval plus the gaps in between and at both ends.
+    fn max_segments(&self) -> usize {
+        2 * self.ranges.len() + 1
+    }
+
+    fn get_range(&self, start: u64, len: u64) -> AOCResult<Vec<(u64, u64)>> {
+        self.get_range_with_cap(start, len, self.max_segments())
+    }
+
+    // Safety net against a bug that stalls the loop below: Fail after
+    // `max_iterations`, rather than looping forever
+    #[instrument(level = "trace", skip(self))]
+    fn get_range_with_cap(
+        &self,
+        start: u64,
+        len: u64,
+        max_iterations: usize,
+    ) -> AOCResult<Vec<(u64, u64)>> {
+        let mut out = Vec::new();
+        let mut start = start;
+        let mut remaining = len;
+        let mut cur_len = 0;
+        let mut iterations = 0;
+        while remaining > 0 {
+            trace!(start, remaining);
+            if iterations >= max_iterations {
+                return Err(AOCError::NoSolution {
+                    msg: format!("mapping range didn't finish after {} iterations", iterations)
+                        .into(),
+                });
+            }
+            iterations += 1;
+            let mut next = u64::MAX;
+            for MapInterval {len, src_start, dest_start} in &self.ranges {
+                if *src_start > start {
+                    next = next.min(*src_start);
+                }
+                match intersect((start, remaining), (*src_start, *len)) {
+                    Some((covered_start, covered_len)) if covered_start == start => {
+                        let cur_dest = dest_start.saturating_add(covered_start - *src_start);
+                        cur_len = covered_len;
+                        trace!(cur_dest, cur_len, "mapped");
+                        out.push((cur_dest, cur_len));
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+
+            if cur_len == 0 {
+                cur_len = (next - start).min(remaining);
+                trace!(start, cur_len, "unmapped");
+                out.push((start, cur_len));
+            }
+            start += cur_len;
+            remaining -= cur_len;
+            cur_len = 0;
+        }
+
+        assert_eq!(len, out.iter().map(|(_, l)| l).sum());
+
+        Ok(out)
+    }
+
+    // Map all (start, len) input ranges, and return the destination ranges
+    // sorted and merged
+    pub fn apply_ranges(&self, inputs: &[(u64, u64)]) -> AOCResult<Vec<(u64, u64)>> {
+        let mut out = Vec::new();
+        for (start, len) in inputs.iter().copied() {
+            out.append(&mut self.get_range(start, len)?);
+        }
+        merge_ranges(&mut out);
+
+        Ok(out)
+    }
+
+    // The map as a piecewise function, one `[src_start, src_end) -> dest_start`
+    // line per interval in order of `src_start`, including the identity
+    // mapping in the gaps between the explicit intervals. Assumes that the
+    // intervals don't overlap. Intervals that include u64::MAX, whose end
+    // doesn't fit into a u64, are printed as `[src_start, u64::MAX]`.
+    pub fn describe(&self) -> String {
+        let mut ranges: Vec<_> = self.ranges.iter().filter(|r| r.len > 0).collect();
+        ranges.sort_unstable_by_key(|r| r.src_start);
+
+        let mut lines = Vec::new();
+        // The first value not covered yet, `None` once u64::MAX is covered
+        let mut cursor = Some(0);
+        for MapInterval {len, src_start, dest_start} in ranges {
+            match cursor {
+                Some(cursor) if *src_start > cursor => {
+                    lines.push(format!("[{}, {}) -> {}", cursor, src_start, cursor));
+                }
+                _ => {}
+            }
+            cursor = src_start.checked_add(*len);
+            match cursor {
+                Some(src_end) => lines.push(format!("[{}, {}) -> {}", src_start, src_end, dest_start)),
+                None => lines.push(format!("[{}, {}] -> {}", src_start, u64::MAX, dest_start)),
+            }
+        }
+        if let Some(cursor) = cursor {
+            lines.push(format!("[{}, {}] -> {}", cursor, u64::MAX, cursor));
+        }
+
+        lines.join("\n")
+    }
+}
+
+// The intervals of a map, one per line
+impl FromStr for AMap {
+    type Err = AOCError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ranges = s
+            .lines()
+            .map(|line| line.trim().parse())
+            .collect::<AOCResult<_>>()?;
+
+        Ok(AMap { ranges })
+    }
+}
+
+// A whole map block, i.e. the `x-to-y map:` header and the intervals
+pub fn parse_map_block(block: &str) -> AOCResult<(String, String, AMap)> {
+    let re = Regex::new("([^-]+)-to-([^-]+) map:").unwrap();
+
+    let (header, intervals) = block.split_once('\n').unwrap_or((block, ""));
+    match re.captures(header.trim()) {
+        Some(cap) => Ok((cap[1].to_owned(), cap[2].to_owned(), intervals.parse()?)),
+        None => Err(AOCError::ParseError { msg: "not a map".into() }),
+    }
+}
+
+// Intersection of two (start, len) ranges, if not empty
+pub fn intersect(a: (u64, u64), b: (u64, u64)) -> Option<(u64, u64)> {
+    let start = a.0.max(b.0);
+    let end = a.0.saturating_add(a.1).min(b.0.saturating_add(b.1));
+    if start < end {
+        Some((start, end - start))
+    } else {
+        None
+    }
+}
+
+// Sort (start, len) ranges and coalesce any that overlap or touch
+pub fn merge_ranges(ranges: &mut Vec<(u64, u64)>) {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, len) in ranges.drain(..) {
+        match merged.last_mut() {
+            Some((prev_start, prev_len)) if start <= prev_start.saturating_add(*prev_len) => {
+                *prev_len = (*prev_len).max(start.saturating_add(len) - *prev_start);
+            }
+            _ => merged.push((start, len)),
+        }
+    }
+
+    *ranges = merged;
+}
+
+#[derive(Clone, Debug)]
+pub struct Data {
+    seeds: Vec<u64>,
+    maps: HashMap<String, (String, AMap)>,
+}
+
+impl Data {
+    // Categories in the order of traversal, from "seed" to "location"
+    pub fn chain(&self) -> AOCResult<Vec<String>> {
+        let mut chain = vec!["seed".to_owned()];
+        let mut key = "seed";
+        while key != "location" {
+            // Every map can be used at most once, more steps imply a cycle
+            if chain.len() > self.maps.len() {
+                return Err(AOCError::ParseError {
+                    msg: format!("cyclic maps: {}", chain.join(" -> ")).into(),
+                });
+            }
+            let (dest, _) = self.maps.get(key).ok_or_else(|| AOCError::ParseError {
+                msg: format!("no map from '{}'", key).into(),
+            })?;
+            chain.push(dest.clone());
+            key = dest;
+        }
+
+        Ok(chain)
+    }
+
+    // Maps in reverse order, from "location" back to "seed", each along with
+    // the name of the category it maps back to
+    pub fn reverse_chain(&self) -> AOCResult<Vec<(String, &AMap)>> {
+        let mut chain = self.chain()?;
+        chain.pop();
+
+        Ok(chain
+            .into_iter()
+            .rev()
+            .map(|from| {
+                let map = &self.maps[&from].1;
+                (from, map)
+            })
+            .collect())
+    }
+
+    // The seeds as (start, len) ranges, as interpreted by part 2
+    pub fn seed_ranges(&self) -> AOCResult<Vec<(u64, u64)>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(AOCError::ParseError {
+                msg: format!("odd number of seeds ({}), expected pairs", self.seeds.len()).into(),
+            });
+        }
+
+        Ok(self.seeds.iter().copied().tuples().collect())
+    }
+}
+
+impl FromStr for Data {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut blocks = split_blocks(input).into_iter();
+
+        let seeds = blocks
+            .next()
+            .unwrap()
+            .trim()
+            .strip_prefix("seeds: ")
+            .unwrap()
+            .split_ascii_whitespace()
+            .map(u64::from_str)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let maps = blocks
+            .map(|block| parse_map_block(block).map(|(from, to, map)| (from, (to, map))))
+            .collect::<AOCResult<_>>()?;
+
+        Ok(Data { seeds, maps })
+    }
+}
+
+// Walk a single seed through all maps to its location
+pub fn map_seed(data: &Data, seed: u64) -> AOCResult<u64> {
+    let chain = data.chain()?;
+    chain[..chain.len() - 1]
+        .iter()
+        .try_fold(seed, |id, key| data.maps[key].1.checked_get(id))
+}
+
+// The locations that the seeds of part 1 end up at
+fn seed_locations(data: &Data) -> AOCResult<HashSet<u64>> {
+    data.seeds.iter().map(|seed| map_seed(data, *seed)).collect()
+}
+
+pub fn part1(data: &Data) -> AOCResult<(u64, HashSet<u64>)> {
+    let locations = seed_locations(data)?;
+
+    let closest = *locations.iter().min().ok_or_else(|| AOCError::NoSolution {
+        msg: "no seeds".into(),
+    })?;
+    Ok((closest, locations))
+}
+
+// The number of distinct locations that the seeds of part 1 end up at
+pub fn distinct_locations(data: &Data) -> AOCResult<usize> {
+    Ok(seed_locations(data)?.len())
+}
+
+// The smallest seed that ends up at `location`, if any. The seeds are taken as
+// a list as in part 1, or as ranges as in part 2 if `as_ranges`.
+pub fn seed_for_location(data: &Data, location: u64, as_ranges: bool) -> AOCResult<Option<u64>> {
+    let mut candidates = vec![location];
+    for (_, map) in data.reverse_chain()? {
+        candidates = candidates.into_iter().flat_map(|id| map.get_inverse(id)).collect();
+    }
+
+    let ranges = if as_ranges { data.seed_ranges()? } else { Vec::new() };
+    let is_seed = |id: &u64| {
+        if as_ranges {
+            ranges.iter().any(|range| intersect(*range, (*id, 1)).is_some())
+        } else {
+            data.seeds.contains(id)
+        }
+    };
+
+    Ok(candidates.into_iter().filter(is_seed).min())
+}
+
+pub fn part2(data: &Data) -> AOCResult<u64> {
+    // Overlapping seed ranges would otherwise be mapped repeatedly
+    let mut ranges = data.seed_ranges()?;
+    merge_ranges(&mut ranges);
+    // `chain` makes sure that all maps exist, and that there are no cycles
+    let chain = data.chain()?;
+    for key in &chain[..chain.len() - 1] {
+        debug!(key, ?ranges);
+        let (_, map) = &data.maps[key];
+        ranges = map.apply_ranges(&ranges)?;
+    }
+
+    debug!(?ranges, "merged location ranges");
+
+    ranges.first().map(|(start, _)| *start).ok_or_else(|| AOCError::NoSolution {
+        msg: "no seed ranges".into(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::{load_input, FromFile};
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $dtype:ty,
+            $compute:path,
+            $expected:expr
+            $(,)?  // allow (optional) trailing comma
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                match $compute(&<$dtype>::from_file($datapath)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(
+        part1,
+        "data/test1.txt",
+        Data,
+        super::part1,
+        (35, HashSet::from([82, 43, 86, 35]))
+    );
+    aoc_test!(part2, "data/test1.txt", Data, super::part2, 46);
+
+    #[test]
+    fn part2_traced() -> AOCResult<()> {
+        // Make sure that the instrumentation is actually exercised
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_test_writer()
+            .finish();
+        let data = Data::from_file("data/test1.txt")?;
+        let result = tracing::subscriber::with_default(subscriber, || super::part2(&data))?;
+        assert_eq!(result, 46);
+
+        Ok(())
+    }
+
+    #[test]
+    fn chain() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(
+            data.chain()?,
+            vec!["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"],
+        );
+
+        let data = Data::from_str("seeds: 1\n\nseed-to-soil map:\n\nwater-to-location map:\n")?;
+        assert!(matches!(data.chain(), Err(AOCError::ParseError { .. })));
+
+        let data = Data::from_str("seeds: 1\n\nseed-to-soil map:\n\nsoil-to-seed map:\n")?;
+        assert!(matches!(data.chain(), Err(AOCError::ParseError { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_broken_chain() -> AOCResult<()> {
+        for input in [
+            "seeds: 1 2\n\nseed-to-soil map:\n\nwater-to-location map:\n",
+            "seeds: 1 2\n\nseed-to-soil map:\n\nsoil-to-seed map:\n",
+        ] {
+            let data = Data::from_str(input)?;
+            assert!(matches!(super::part2(&data), Err(AOCError::ParseError { .. })), "{:?}", input);
+        }
+
+        let data = Data { seeds: vec![], ..Data::from_file("data/test1.txt")? };
+        assert!(matches!(super::part2(&data), Err(AOCError::NoSolution { .. })));
+        assert!(matches!(super::part1(&data), Err(AOCError::NoSolution { .. })));
+        assert_eq!(super::distinct_locations(&data)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reverse_chain() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        let chain = data.reverse_chain()?;
+        assert_eq!(
+            chain.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(),
+            vec!["humidity", "temperature", "light", "water", "fertilizer", "soil", "seed"],
+        );
+
+        // The first map is the humidity-to-location one
+        assert_eq!(chain[0].1.get(78), 82);
+
+        for input in [
+            "seeds: 1\n\nseed-to-soil map:\n\nwater-to-location map:\n",
+            "seeds: 1\n\nseed-to-soil map:\n\nsoil-to-seed map:\n",
+        ] {
+            let data = Data::from_str(input)?;
+            assert!(matches!(data.reverse_chain(), Err(AOCError::ParseError { .. })), "{:?}", input);
+            assert!(
+                matches!(super::seed_for_location(&data, 1, false), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_map_block() -> AOCResult<()> {
+        let (from, to, map) = super::parse_map_block("seed-to-soil map:\n50 98 2\n52 50 48\n")?;
+        assert_eq!((from.as_str(), to.as_str()), ("seed", "soil"));
+        assert_eq!(map.ranges.len(), 2);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(53), 55);
+        assert_eq!(map.get(10), 10);
+
+        // No intervals at all
+        let (_, _, map) = super::parse_map_block("a-to-b map:")?;
+        assert!(map.ranges.is_empty());
+
+        assert!(matches!(
+            super::parse_map_block("seed-to-soil:\n50 98 2\n"),
+            Err(AOCError::ParseError { .. })
+        ));
+        assert!(matches!(
+            super::parse_map_block("seed-to-soil map:\n50 98\n"),
+            Err(AOCError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn describe() -> AOCResult<()> {
+        let (_, _, map) = super::parse_map_block("seed-to-soil map:\n50 98 2\n52 50 48\n")?;
+        assert_eq!(
+            map.describe(),
+            "[0, 50) -> 0\n\
+             [50, 98) -> 52\n\
+             [98, 100) -> 50\n\
+             [100, 18446744073709551615] -> 100"
+        );
+
+        // Gaps in between, and an interval ending right before u64::MAX
+        let map: AMap = "0 10 5\n100 18446744073709551605 10\n".parse()?;
+        assert_eq!(
+            map.describe(),
+            "[0, 10) -> 0\n\
+             [10, 15) -> 0\n\
+             [15, 18446744073709551605) -> 15\n\
+             [18446744073709551605, 18446744073709551615) -> 100\n\
+             [18446744073709551615, 18446744073709551615] -> 18446744073709551615"
+        );
+
+        // An interval including u64::MAX, leaving no trailing gap
+        let map: AMap = "100 18446744073709551606 10\n".parse()?;
+        assert_eq!(
+            map.describe(),
+            "[0, 18446744073709551606) -> 0\n\
+             [18446744073709551606, 18446744073709551615] -> 100"
+        );
+
+        let map: AMap = "".parse()?;
+        assert_eq!(map.describe(), "[0, 18446744073709551615] -> 0");
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_inverse() -> AOCResult<()> {
+        let map: AMap = "50 98 2\n52 50 48\n".parse()?;
+        assert_eq!(map.get_inverse(81), vec![79]);
+        assert_eq!(map.get_inverse(14), vec![14]);
+        // 50 and 51 are mapped to, but also themselves mapped away
+        assert_eq!(map.get_inverse(50), vec![98]);
+        // 99 is mapped away, but 97 is mapped to it
+        assert_eq!(map.get_inverse(99), vec![97]);
+
+        // Collision of the identity mapping with an interval
+        let map: AMap = "10 0 5\n".parse()?;
+        assert_eq!(map.get_inverse(12), vec![2, 12]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn seed_for_location() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::seed_for_location(&data, 35, false)?, Some(13));
+        assert_eq!(super::seed_for_location(&data, 82, false)?, Some(79));
+        assert_eq!(super::seed_for_location(&data, 46, false)?, None);
+
+        assert_eq!(super::seed_for_location(&data, 46, true)?, Some(82));
+        for location in 0..100 {
+            let seed = super::seed_for_location(&data, location, true)?;
+            if let Some(seed) = seed {
+                assert_eq!(super::map_seed(&data, seed)?, location);
+            }
+            // 46 is the closest location reached from any seed range
+            if location < 46 {
+                assert_eq!(seed, None);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn map_seed() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::map_seed(&data, 79)?, 82);
+        assert_eq!(super::map_seed(&data, 14)?, 43);
+        assert_eq!(super::map_seed(&data, 55)?, 86);
+        assert_eq!(super::map_seed(&data, 13)?, 35);
+
+        let data = Data::from_str("seeds: 1\n\nseed-to-soil map:\n50 98 2\n")?;
+        assert!(matches!(super::map_seed(&data, 1), Err(AOCError::ParseError { .. })));
+
+        // Broken and cyclic chains
+        for input in [
+            "seeds: 1\n\nseed-to-soil map:\n\nwater-to-location map:\n",
+            "seeds: 1\n\nseed-to-soil map:\n\nsoil-to-seed map:\n",
+        ] {
+            let data = Data::from_str(input)?;
+            assert!(matches!(super::map_seed(&data, 1), Err(AOCError::ParseError { .. })), "{:?}", input);
+            assert!(matches!(super::part1(&data), Err(AOCError::ParseError { .. })), "{:?}", input);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn distinct_locations() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(super::distinct_locations(&data)?, 4);
+
+        // Duplicate seeds end up at the same location
+        let data = Data { seeds: vec![79, 14, 79], ..data };
+        assert_eq!(super::distinct_locations(&data)?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn odd_seed_count() -> AOCResult<()> {
+        let data = Data::from_str("seeds: 79 14 55\n\nseed-to-location map:\n50 98 2\n")?;
+        assert_eq!(super::part1(&data)?.0, 14);
+        assert!(matches!(data.seed_ranges(), Err(AOCError::ParseError { .. })));
+        assert!(matches!(super::part2(&data), Err(AOCError::ParseError { .. })));
+
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(data.seed_ranges()?, vec![(79, 14), (55, 13)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn overlapping_seed_ranges() -> AOCResult<()> {
+        let input = load_input("data/test1.txt")?;
+        let input = input.replace("seeds: 79 14 55 13", "seeds: 79 14 55 13 80 5 60 10");
+        let data = Data::from_str(&input)?;
+
+        let mut ranges = data.seed_ranges()?;
+        super::merge_ranges(&mut ranges);
+        assert_eq!(ranges, vec![(55, 15), (79, 14)]);
+        assert_eq!(super::part2(&data)?, 46);
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_ranges() -> AOCResult<()> {
+        let (_, _, map) = super::parse_map_block("seed-to-soil map:\n50 98 2\n52 50 48\n")?;
+        // Overlapping inputs, and outputs that touch after mapping
+        assert_eq!(
+            map.apply_ranges(&[(40, 15), (45, 10), (98, 2), (100, 5)])?,
+            vec![(40, 17), (100, 5)]
+        );
+        assert_eq!(map.apply_ranges(&[(96, 4)])?, vec![(50, 2), (98, 2)]);
+        assert_eq!(map.apply_ranges(&[])?, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_range_cap() -> AOCResult<()> {
+        // The sample stays well under the cap
+        let data = Data::from_file("data/test1.txt")?;
+        for (_, map) in data.maps.values() {
+            for (start, len) in data.seed_ranges()? {
+                let segments = map.get_range(start, len)?;
+                assert!(segments.len() <= map.max_segments(), "{:?}", segments);
+            }
+        }
+
+        // Worst case, alternating gaps and intervals
+        let map: AMap = "100 10 1\n200 20 1\n".parse()?;
+        assert_eq!(
+            map.get_range(0, 30)?,
+            vec![(0, 10), (100, 1), (11, 9), (200, 1), (21, 9)]
+        );
+        assert!(matches!(
+            map.get_range_with_cap(0, 30, 4),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn beyond_u32() -> AOCResult<()> {
+        // Wouldn't fit into a 32-bit usize
+        let map: AMap = "10000000000 4294967296 100\n".parse()?;
+        assert_eq!(map.get(4294967300), 10000000004);
+        assert_eq!(map.get(4294967396), 4294967396);
+        assert_eq!(
+            map.get_range(4294967290, 20)?,
+            vec![(4294967290, 6), (10000000000, 14)]
+        );
+
+        // Intervals reaching up to u64::MAX
+        let map: AMap = format!("0 {} 10\n", u64::MAX - 9).parse()?;
+        assert_eq!(map.get(u64::MAX), 9);
+        assert_eq!(super::intersect((u64::MAX - 1, 1), (u64::MAX - 9, 10)), Some((u64::MAX - 1, 1)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_intervals() -> AOCResult<()> {
+        for interval in ["50 98 0", "50 98", "50 98 2 1", "50 x 2", "-1 98 2"] {
+            assert!(
+                matches!(interval.parse::<MapInterval>(), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                interval
+            );
+        }
+        match "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 0\n".parse::<Data>() {
+            Err(AOCError::ParseError { msg }) => assert_eq!(msg, "empty range: '52 50 0'"),
+            result => panic!("{:?}", result),
+        }
+
+        // Reaching exactly up to u64::MAX is fine, but not beyond
+        format!("{} 0 10", u64::MAX - 9).parse::<MapInterval>()?;
+        for interval in [format!("{} 0 11", u64::MAX - 9), format!("0 {} 11", u64::MAX - 9)] {
+            assert!(
+                matches!(interval.parse::<MapInterval>(), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                interval
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn checked_get() -> AOCResult<()> {
+        let map: AMap = "50 98 2\n52 50 48\n".parse()?;
+        for index in [0, 49, 50, 97, 98, 99, 100] {
+            assert_eq!(map.checked_get(index)?, map.get(index));
+        }
+
+        // Bypasses the validation in `MapInterval::from_str`
+        let map = AMap {
+            ranges: vec![MapInterval { len: 10, src_start: 0, dest_start: u64::MAX - 4 }],
+        };
+        assert_eq!(map.checked_get(4)?, u64::MAX);
+        assert!(matches!(map.checked_get(5), Err(AOCError::Overflow)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn intersect() {
+        // disjoint
+        assert_eq!(super::intersect((1, 3), (10, 2)), None);
+        assert_eq!(super::intersect((10, 2), (1, 3)), None);
+        // touching
+        assert_eq!(super::intersect((1, 3), (4, 2)), None);
+        // overlapping
+        assert_eq!(super::intersect((1, 5), (4, 3)), Some((4, 2)));
+        // nested
+        assert_eq!(super::intersect((1, 10), (4, 2)), Some((4, 2)));
+        assert_eq!(super::intersect((4, 2), (1, 10)), Some((4, 2)));
+        // empty
+        assert_eq!(super::intersect((4, 0), (1, 10)), None);
+    }
+
+    #[test]
+    fn merge_ranges() {
+        let mut ranges = vec![(10, 1), (4, 2), (1, 3)];
+        super::merge_ranges(&mut ranges);
+        assert_eq!(ranges, vec![(1, 5), (10, 1)]);
+
+        let mut ranges = vec![(5, 10), (1, 3), (7, 2)];
+        super::merge_ranges(&mut ranges);
+        assert_eq!(ranges, vec![(1, 3), (5, 10)]);
+    }
+}
@@ -0,0 +1,355 @@
+use aoc_common::{AOCError, AOCResult};
+use std::str::FromStr;
+use tracing::debug;
+
+#[derive(Clone, Debug)]
+pub struct Data {
+    races: Vec<(u64, u64)>,
+}
+
+impl FromStr for Data {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input.lines();
+
+        let times = lines
+            .next()
+            .unwrap()
+            .split_once(':')
+            .unwrap()
+            .1
+            .split_ascii_whitespace()
+            .map(u64::from_str)
+            .collect::<Result<Vec<_>, _>>().unwrap();
+        let distances = lines
+            .next()
+            .unwrap()
+            .split_once(':')
+            .unwrap()
+            .1
+            .split_ascii_whitespace()
+            .map(u64::from_str)
+            .collect::<Result<Vec<_>, _>>().unwrap();
+
+        let races = times.iter().copied().zip(distances).collect();
+
+        Ok(Data { races })
+    }
+}
+
+// The single race of part 2, where the numbers are written with whitespace in
+// between. Times and distances are u128 in case the concatenated digits don't
+// fit into u64.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SingleRace {
+    time: u128,
+    distance: u128,
+}
+
+impl SingleRace {
+    pub fn new(time: u128, distance: u128) -> Self {
+        Self { time, distance }
+    }
+
+    // The same race as parsed by `from_str`, but by concatenating the numbers
+    // of the individual races
+    pub fn from_data(data: &Data) -> AOCResult<Self> {
+        let concat = |numbers: Vec<u64>| -> AOCResult<u128> {
+            let digits: String = numbers.iter().map(u64::to_string).collect();
+            digits.parse().map_err(|_| AOCError::ParseError {
+                msg: format!("invalid number '{}'", digits).into(),
+            })
+        };
+        let (times, distances) = data.races.iter().copied().unzip();
+
+        Ok(SingleRace::new(concat(times)?, concat(distances)?))
+    }
+}
+
+impl FromStr for SingleRace {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input.lines();
+
+        let mut parse_line = |name: &str| -> AOCResult<u128> {
+            let line = lines.next().ok_or_else(|| AOCError::ParseError {
+                msg: format!("missing {} line", name).into(),
+            })?;
+            let (_, numbers) = line.split_once(':').ok_or_else(|| AOCError::ParseError {
+                msg: format!("invalid {} line: '{}'", name, line).into(),
+            })?;
+            let digits = numbers
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect::<String>();
+            digits.parse().map_err(|_| AOCError::ParseError {
+                msg: format!("invalid number '{}'", digits).into(),
+            })
+        };
+        let time = parse_line("time")?;
+        let distance = parse_line("distance")?;
+
+        Ok(SingleRace { time, distance })
+    }
+}
+
+pub fn part1(data: &Data) -> AOCResult<(u64, Vec<u64>)> {
+    part1_with_check(data, false)
+}
+
+// A race whose record can't be beaten makes the product zero. That's a valid
+// answer, but all actual puzzle races are winnable, so it likely indicates a
+// corrupt input: Fail on it if `strict`, which the binary always is.
+pub fn part1_with_check(data: &Data, strict: bool) -> AOCResult<(u64, Vec<u64>)> {
+    let mut winning_combos = Vec::new();
+    for (time, distance) in &data.races {
+        let wins = (0..=*time)
+            .filter(|charge| beats((*time).into(), (*distance).into(), (*charge).into()))
+            .count() as u64;
+        if strict && wins == 0 {
+            return Err(AOCError::NoSolution {
+                msg: format!("race of {} ms with record {} mm can't be won", time, distance).into(),
+            });
+        }
+        winning_combos.push(wins);
+    }
+
+    let total = winning_combos
+        .iter()
+        .try_fold(1u64, |acc, wins| acc.checked_mul(*wins))
+        .ok_or(AOCError::Overflow)?;
+
+    Ok((total, winning_combos))
+}
+
+// Longest race for which `winning_charges` is willing to list the charges
+pub const MAX_CHARGE_LIST_TIME: u64 = 1_000_000;
+
+// All charges that beat the record, in increasing order. This is only meant
+// for small races, the part 2 race would need gigabytes.
+pub fn winning_charges(time: u64, distance: u64) -> AOCResult<Vec<u64>> {
+    if time > MAX_CHARGE_LIST_TIME {
+        return Err(AOCError::NoSolution {
+            msg: format!(
+                "race of {} ms is too long to list charges, the limit is {} ms",
+                time, MAX_CHARGE_LIST_TIME,
+            ).into(),
+        });
+    }
+
+    Ok((0..=time)
+        .filter(|charge| beats(time.into(), distance.into(), (*charge).into()))
+        .collect())
+}
+
+// Races too long to list their charges are only summarised by the count
+pub fn report_part1(data: &Data) -> AOCResult<()> {
+    for (time, distance) in &data.races {
+        if *time > MAX_CHARGE_LIST_TIME {
+            let count = part2(&SingleRace::new((*time).into(), (*distance).into()))?;
+            println!(
+                "Race of {} ms with record {} mm is won by {} charges, too many to list",
+                time, distance, count,
+            );
+            continue;
+        }
+        let charges = winning_charges(*time, *distance)?;
+        println!(
+            "Race of {} ms with record {} mm is won by charging for {:?} ms",
+            time, distance, charges,
+        );
+    }
+    Ok(())
+}
+
+// Distance travelled when charging for `charge` out of `time` (which must not
+// be less than `charge`), `None` if it doesn't fit into u128
+pub fn distance_for_charge(time: u128, charge: u128) -> Option<u128> {
+    (time - charge).checked_mul(charge)
+}
+
+// Whether charging for `charge` beats `distance`, a product that doesn't even
+// fit into u128 certainly does
+pub fn beats(time: u128, distance: u128, charge: u128) -> bool {
+    distance_for_charge(time, charge).is_none_or(|d| d > distance)
+}
+
+pub fn part2_from_data(data: &Data) -> AOCResult<u128> {
+    part2(&SingleRace::from_data(data)?)
+}
+
+pub fn part2(race: &SingleRace) -> AOCResult<u128> {
+    let SingleRace { time, distance } = *race;
+    debug!(%time, %distance);
+
+    // (t - c) c is symmetric about t / 2 and increasing below it, so binary
+    // search for the first winning charge c1; the last one is t - c1.
+    // This avoids the precision loss of solving the quadratic in f64.
+    let half = time / 2;
+    if !beats(time, distance, half) {
+        return Ok(0);
+    }
+
+    let (mut lo, mut hi) = (0, half);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if beats(time, distance, mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    Ok(time - 2 * lo + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::FromFile;
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $dtype:ty,
+            $compute:path,
+            $expected:expr
+            $(,)?  // allow (optional) trailing comma
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                match $compute(&<$dtype>::from_file($datapath)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(
+        part1,
+        "data/test1.txt",
+        Data,
+        super::part1,
+        (288, vec![4, 8, 9])
+    );
+    aoc_test!(part2, "data/test1.txt", SingleRace, super::part2, 71503);
+
+    #[test]
+    fn distance_for_charge() {
+        // Not moving at all, or not having any time left to move
+        assert_eq!(super::distance_for_charge(7, 0), Some(0));
+        assert_eq!(super::distance_for_charge(7, 7), Some(0));
+        // Beats the record of 9 mm in the first sample race
+        assert_eq!(super::distance_for_charge(7, 2), Some(10));
+        assert!(beats(7, 9, 2));
+        assert!(!beats(7, 10, 2));
+
+        assert_eq!(super::distance_for_charge(u128::MAX, u128::MAX / 2), None);
+    }
+
+    #[test]
+    fn part1_overflow() {
+        // 999 winning charges each, 999^7 > u64::MAX
+        let data = Data { races: vec![(1000, 0); 7] };
+        assert!(matches!(super::part1(&data), Err(AOCError::Overflow)));
+
+        let data = Data { races: vec![(1000, 0); 6] };
+        assert_eq!(super::part1(&data).unwrap().0, 999u64.pow(6));
+    }
+
+    #[test]
+    fn unwinnable_race() -> AOCResult<()> {
+        // 4 * 5 = 20 is the best possible distance in the second race
+        let data = Data { races: vec![(7, 9), (9, 20)] };
+        assert_eq!(super::part1(&data)?, (0, vec![4, 0]));
+        assert!(matches!(
+            part1_with_check(&data, true),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        let data = Data { races: vec![(7, 9), (9, 19)] };
+        assert_eq!(part1_with_check(&data, true)?, (8, vec![4, 2]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_from_data() -> AOCResult<()> {
+        let data = Data::from_file("data/test1.txt")?;
+        assert_eq!(SingleRace::from_data(&data)?, SingleRace::from_file("data/test1.txt")?);
+        assert_eq!(super::part2_from_data(&data)?, 71503);
+
+        Ok(())
+    }
+
+    #[test]
+    fn single_race() -> AOCResult<()> {
+        assert_eq!(super::part2(&SingleRace::new(7, 9))?, 4);
+        assert_eq!(super::part2(&SingleRace::new(30, 200))?, 9);
+        assert_eq!(super::part2(&SingleRace::new(3, 10))?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_single_race() {
+        for input in ["", "Time: 7 15 30\n", "Time 7 15 30\nDistance: 9 40 200\n", "Time: 7\nDistance: \n"] {
+            assert!(
+                matches!(SingleRace::from_str(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn winning_charges() -> AOCResult<()> {
+        assert_eq!(super::winning_charges(7, 9)?, vec![2, 3, 4, 5]);
+        assert_eq!(super::winning_charges(30, 200)?, (11..=19).collect::<Vec<_>>());
+        assert_eq!(super::winning_charges(3, 10)?, vec![]);
+
+        let data = Data::from_file("data/test1.txt")?;
+        for (time, distance) in &data.races {
+            let wins = super::winning_charges(*time, *distance)?.len() as u64;
+            let race = SingleRace { time: (*time).into(), distance: (*distance).into() };
+            assert_eq!(u128::from(wins), super::part2(&race)?);
+        }
+
+        assert!(matches!(
+            super::winning_charges(MAX_CHARGE_LIST_TIME + 1, 0),
+            Err(AOCError::NoSolution { .. })
+        ));
+        // The report only summarises such races rather than failing
+        let data = Data { races: vec![(7, 9), (MAX_CHARGE_LIST_TIME + 1, 0)] };
+        report_part1(&data)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_beyond_u64() -> AOCResult<()> {
+        // t = 2^65 - 2 doesn't fit into u64, only t / 2 wins
+        let input = "Time: 36893488147419103230\n\
+                     Distance: 340282366920938463426481119284349108224\n";
+        let race = SingleRace::from_str(input)?;
+        assert!(race.time > u64::MAX as u128);
+        assert_eq!(super::part2(&race)?, 1);
+
+        // Every charge except 0 and t wins
+        let time = race.time;
+        assert_eq!(super::part2(&SingleRace { time, distance: 0 })?, time - 1);
+
+        // Nothing wins
+        let distance = race.distance + 1;
+        assert_eq!(super::part2(&SingleRace { time, distance })?, 0);
+
+        Ok(())
+    }
+}
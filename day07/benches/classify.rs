@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day07::{typ, typ_nested};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Card ranks in joker mode, i.e. with 1 as the joker
+const CARDS: [u8; 13] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12, 13, 14];
+
+fn random_hands(n: usize) -> Vec<[u8; 5]> {
+    let mut rng = StdRng::seed_from_u64(7);
+    (0..n)
+        .map(|_| {
+            let mut hand = [0u8; 5];
+            hand.iter_mut().for_each(|c| *c = CARDS[rng.gen_range(0..CARDS.len())]);
+            hand
+        })
+        .collect()
+}
+
+fn classify(c: &mut Criterion) {
+    let hands = random_hands(10_000);
+
+    let mut group = c.benchmark_group("classify");
+    for (name, wildcard) in [("no wildcard", None), ("jokers", Some(1))] {
+        group.bench_function(format!("nested match, {}", name), |b| {
+            b.iter(|| {
+                hands.iter().map(|h| typ_nested(black_box(h), wildcard)).max()
+            })
+        });
+        group.bench_function(format!("substitution, {}", name), |b| {
+            b.iter(|| {
+                hands.iter().map(|h| typ(black_box(h), wildcard)).max()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, classify);
+criterion_main!(benches);
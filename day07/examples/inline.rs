@@ -0,0 +1,21 @@
+// Solve both parts for an inline puzzle input, using day07 as a library
+//
+//     cargo run -p day07 --example inline
+
+use aoc_common::AOCResult;
+use day07::{part1, part2, read_part1, read_part2};
+
+const INPUT: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+fn main() -> AOCResult<()> {
+    println!("Part 1: {}", part1(&mut read_part1(INPUT)?)?);
+    println!("Part 2: {}", part2(&mut read_part2(INPUT)?)?);
+
+    Ok(())
+}
@@ -0,0 +1,649 @@
+use aoc_common::{AOCError, AOCResult, Counter};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Card {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Card {
+    // Strength of the card, in joker mode the J is the weakest card
+    pub fn rank(self, joker_mode: bool) -> u8 {
+        match self {
+            Card::Jack if joker_mode => 1,
+            _ => self as u8 + 2,
+        }
+    }
+}
+
+impl TryFrom<char> for Card {
+    type Error = AOCError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            '2' => Card::Two,
+            '3' => Card::Three,
+            '4' => Card::Four,
+            '5' => Card::Five,
+            '6' => Card::Six,
+            '7' => Card::Seven,
+            '8' => Card::Eight,
+            '9' => Card::Nine,
+            'T' => Card::Ten,
+            'J' => Card::Jack,
+            'Q' => Card::Queen,
+            'K' => Card::King,
+            'A' => Card::Ace,
+            _ => {
+                return Err(AOCError::ParseError {
+                    msg: format!("invalid card '{}'", c).into(),
+                })
+            }
+        })
+    }
+}
+
+fn ranks(hand: &[Card; 5], joker_mode: bool) -> [u8; 5] {
+    hand.map(|c| c.rank(joker_mode))
+}
+
+#[derive(PartialOrd, Ord, PartialEq, Eq, Clone, Debug)]
+pub enum HandType {
+    FiveOfAKind = 10,
+    FourOfAKind = 9,
+    FullHouse = 8,
+    ThreeOfAKind = 7,
+    TwoPair = 6,
+    OnePair = 5,
+    HighCard = 4,
+}
+
+#[derive(Clone, Debug)]
+pub struct Hand {
+    bid: u32,
+    hand: [Card; 5],
+}
+
+#[derive(Clone, Debug)]
+pub struct HandWithJokers {
+    bid: u32,
+    hand: [Card; 5],
+}
+
+// Classify a hand, where cards equal to `wildcard` (if any) take whatever
+// value results in the strongest hand.
+//
+// That's always achieved by turning the wildcards into the most frequent
+// among the other cards, such that the hand can then be classified without
+// any wildcards.
+//
+// The type then follows from the size of the largest group of equal cards
+// and the number of distinct cards (which the wildcards don't change).
+pub fn typ<C: Copy + Eq + Hash>(hand: &[C; 5], wildcard: Option<C>) -> HandType {
+    let counts: Counter<C> = hand.iter().copied().filter(|c| Some(*c) != wildcard).collect();
+    let wildcard_count = hand.iter().filter(|c| Some(**c) == wildcard).count();
+
+    match (counts.max_count() + wildcard_count, counts.len()) {
+        (5, _) => HandType::FiveOfAKind,
+        (4, _) => HandType::FourOfAKind,
+        (3, 2) => HandType::FullHouse,
+        (3, _) => HandType::ThreeOfAKind,
+        (2, 3) => HandType::TwoPair,
+        (2, _) => HandType::OnePair,
+        _ => HandType::HighCard,
+    }
+}
+
+// Original implementation of `typ`, which handles the wildcard by a case
+// distinction. Kept around to benchmark against the substitution approach.
+pub fn typ_nested<C: Copy + Eq + Hash>(hand: &[C; 5], wildcard: Option<C>) -> HandType {
+    let mut counts: HashMap<C, u8> = HashMap::new();
+    hand.iter().for_each(|c| { *counts.entry(*c).or_default() += 1; });
+
+    let wildcard_count = wildcard.and_then(|w| counts.get(&w));
+    match counts.values().copied().max().unwrap() {
+        5 => HandType::FiveOfAKind,
+        4 => {
+            match wildcard_count {
+                Some(4) => HandType::FiveOfAKind,
+                Some(1) => HandType::FiveOfAKind,
+                _ => HandType::FourOfAKind,
+            }
+        },
+        3 => {
+            if counts.values().any(|c| *c == 2) {
+                match wildcard_count {
+                    Some(3) => HandType::FiveOfAKind,  // 3 J + 1 pair
+                    Some(2) => HandType::FiveOfAKind,  // 2 J + triplett
+                    None => HandType::FullHouse,  // no j, but 2 + 3
+                    _ => unreachable!(),
+                }
+            } else {
+                match wildcard_count {
+                    Some(3) => HandType::FourOfAKind,  // triplett of J + 2 single
+                    Some(1) => HandType::FourOfAKind, // triplett + single J
+                    None => HandType::ThreeOfAKind,  // triplett + 2 single
+                    _ => unreachable!(),
+                }
+            }
+        },
+        2 => {
+            if counts.values().filter(|c| **c == 2).count() == 2 {
+                match wildcard_count {
+                    Some(2) => HandType::FourOfAKind,  // 2 pairs, one of which J
+                    Some(1) => HandType::FullHouse,  // 2 pairs + 1 J
+                    None => HandType::TwoPair,  // just 2 pairs
+                    _ => unreachable!(),
+                }
+            } else {
+                match wildcard_count {
+                    Some(2) => HandType::ThreeOfAKind,  // 1 pair of J, 3 single
+                    Some(1) => HandType::ThreeOfAKind,  // 1 pair, 1 J, 2 other single
+                    None => HandType::OnePair,  // 1 pair, 3 single
+                    _ => unreachable!(),
+                }
+            }
+        },
+        1 => {
+            match wildcard_count {
+                Some(1) => HandType::OnePair,  // singles, 1 of which J
+                None => HandType::HighCard,  // single cards only
+                _ => unreachable!(),
+            }
+        },
+        _ => unreachable!(),
+    }
+}
+
+impl Hand {
+    fn typ(&self) -> HandType {
+        typ(&self.hand, None)
+    }
+
+    // Ranking order of the puzzle: By type first, then card by card. Bids
+    // don't matter.
+    //
+    // Since every card has a distinct rank, ties only occur between hands
+    // with identical cards, i.e. `same_strength` agrees with `==` (which
+    // compares the cards, and ignores bids as well).
+    pub fn strength_cmp(&self, other: &Self) -> Ordering {
+        self.typ()
+            .cmp(&other.typ())
+            .then_with(|| ranks(&self.hand, false).cmp(&ranks(&other.hand, false)))
+    }
+
+    pub fn same_strength(&self, other: &Self) -> bool {
+        self.strength_cmp(other) == Ordering::Equal
+    }
+}
+
+impl HandWithJokers {
+    fn typ(&self) -> HandType {
+        typ(&self.hand, Some(Card::Jack))
+    }
+
+    // See `Hand::strength_cmp`, with jokers as wildcards and the weakest card
+    pub fn strength_cmp(&self, other: &Self) -> Ordering {
+        self.typ()
+            .cmp(&other.typ())
+            .then_with(|| ranks(&self.hand, true).cmp(&ranks(&other.hand, true)))
+    }
+
+    pub fn same_strength(&self, other: &Self) -> bool {
+        self.strength_cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.hand == other.hand
+    }
+}
+
+impl Eq for Hand { }
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.strength_cmp(other)
+    }
+}
+
+impl PartialEq for HandWithJokers {
+    fn eq(&self, other: &Self) -> bool {
+        self.hand == other.hand
+    }
+}
+
+impl Eq for HandWithJokers { }
+
+impl PartialOrd for HandWithJokers {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HandWithJokers {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.strength_cmp(other)
+    }
+}
+
+pub fn type_histogram(hands: &[Hand]) -> BTreeMap<HandType, usize> {
+    let mut histogram = BTreeMap::new();
+    hands.iter().for_each(|h| { *histogram.entry(h.typ()).or_default() += 1; });
+    histogram
+}
+
+pub fn type_histogram_jokers(hands: &[HandWithJokers]) -> BTreeMap<HandType, usize> {
+    let mut histogram = BTreeMap::new();
+    hands.iter().for_each(|h| { *histogram.entry(h.typ()).or_default() += 1; });
+    histogram
+}
+
+// Each hand's type, card ranks and bid, in input order
+pub fn classify_all(hands: &[Hand]) -> Vec<(HandType, [u8; 5], u32)> {
+    hands.iter().map(|h| (h.typ(), ranks(&h.hand, false), h.bid)).collect()
+}
+
+pub fn classify_all_jokers(hands: &[HandWithJokers]) -> Vec<(HandType, [u8; 5], u32)> {
+    hands.iter().map(|h| (h.typ(), ranks(&h.hand, true), h.bid)).collect()
+}
+
+// Check that no card occurs more than `N` times, and that exactly `N` cards
+// are present. The frequencies are checked first, such that e.g. six of a
+// kind is reported as such rather than as a hand of the wrong size.
+pub fn validate_cards<C: Copy + Eq + Hash, const N: usize>(cards: &[C]) -> AOCResult<[C; N]> {
+    let counts: Counter<C> = cards.iter().copied().collect();
+    if counts.max_count() > N {
+        return Err(AOCError::ParseError {
+            msg: format!("card appears {} times in a hand of {}", counts.max_count(), N).into(),
+        });
+    }
+    if cards.len() != N {
+        return Err(AOCError::ParseError {
+            msg: format!("hand has {} cards instead of {}", cards.len(), N).into(),
+        });
+    }
+
+    Ok(cards.try_into().unwrap())
+}
+
+// A hand, optionally followed by its bid, which defaults to 0 if missing
+fn parse_hand(line: &str) -> AOCResult<(u32, [Card; 5])> {
+    let parse_error = || AOCError::ParseError {
+        msg: format!("invalid line: '{}'", line).into(),
+    };
+    let mut tokens = line.split_ascii_whitespace();
+    let hand_str = tokens.next().ok_or_else(parse_error)?;
+    let bid = match tokens.next() {
+        Some(bid) => bid.parse().map_err(|_| parse_error())?,
+        None => 0,
+    };
+    if tokens.next().is_some() {
+        return Err(parse_error());
+    }
+    let cards = hand_str
+        .chars()
+        .map(Card::try_from)
+        .collect::<AOCResult<Vec<_>>>()?;
+    let hand = validate_cards(&cards)?;
+
+    Ok((bid, hand))
+}
+
+pub fn read_part1(input: &str) -> AOCResult<Vec<Hand>> {
+    input.lines()
+        .map(|l| parse_hand(l).map(|(bid, hand)| Hand { bid, hand }))
+        .collect()
+}
+
+pub fn read_part2(input: &str) -> AOCResult<Vec<HandWithJokers>> {
+    input.lines()
+        .map(|l| parse_hand(l).map(|(bid, hand)| HandWithJokers { bid, hand }))
+        .collect()
+}
+
+// The hands from weakest to strongest, ignoring bids
+pub fn rank_only(hands: &mut [Hand]) -> Vec<Hand> {
+    hands.sort_unstable();
+    hands.to_vec()
+}
+
+pub fn part1(data: &mut [Hand]) -> AOCResult<u64> {
+    data.sort_unstable();
+
+    Ok(data.iter().enumerate().map(|(rank, hand)| {
+        (hand.bid as u64) * (rank as u64 + 1)
+    }).sum::<u64>())
+}
+
+pub fn part2(data: &mut [HandWithJokers]) -> AOCResult<u64> {
+    data.sort_unstable();
+
+    Ok(data.iter().enumerate().map(|(rank, hand)| {
+        (hand.bid as u64) * (rank as u64 + 1)
+    }).sum::<u64>())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::load_input;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeSet;
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $read_data:path,
+            $compute:path,
+            $expected:expr
+            $(,)?  // allow (optional) trailing comma
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                let input = load_input($datapath)?;
+                match $compute(&mut $read_data(&input)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(part1, "data/test1.txt", read_part1, super::part1, 6440);
+    aoc_test!(part2, "data/test1.txt", read_part2, super::part2, 5905);
+
+    #[test]
+    fn wildcard() -> AOCResult<()> {
+        let hands = read_part1("QQQJA 1\nT55Q5 2\nQQQQQ 3\nQ2345 4\nQ2245 5\nQQ23Q 6\n")?;
+        let types: Vec<_> = hands.iter().map(|h| typ(&h.hand, Some(Card::Queen))).collect();
+        assert_eq!(
+            types,
+            vec![
+                HandType::FourOfAKind,
+                HandType::FourOfAKind,
+                HandType::FiveOfAKind,
+                HandType::OnePair,
+                HandType::ThreeOfAKind,
+                HandType::FourOfAKind,
+            ]
+        );
+
+        // Without wildcard
+        assert_eq!(hands[0].typ(), HandType::ThreeOfAKind);
+        assert_eq!(typ(&hands[0].hand, None), HandType::ThreeOfAKind);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hand_length() {
+        for input in ["32T3K 765\nT55J 684\n", "32T3K 765\nT55J55 684\n", " 1\n"] {
+            assert!(
+                matches!(read_part1(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+            assert!(
+                matches!(read_part2(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn validate_cards() {
+        assert_eq!(super::validate_cards::<_, 5>(&[Card::Ace; 5]).ok(), Some([Card::Ace; 5]));
+
+        // Six of a kind
+        let malformed = [Card::Ace; 6];
+        match super::validate_cards::<_, 5>(&malformed) {
+            Err(AOCError::ParseError { msg }) => assert_eq!(msg, "card appears 6 times in a hand of 5"),
+            result => panic!("{:?}", result),
+        }
+        match read_part1("AAAAAA 1\n") {
+            Err(AOCError::ParseError { msg }) => assert_eq!(msg, "card appears 6 times in a hand of 5"),
+            result => panic!("{:?}", result),
+        }
+
+        // Too few or too many, but distinct enough cards
+        for cards in [&malformed[..4], &[Card::Ace, Card::Two, Card::Ace, Card::Two, Card::Ace, Card::Two]] {
+            match super::validate_cards::<_, 5>(cards) {
+                Err(AOCError::ParseError { msg }) => assert!(msg.starts_with("hand has"), "{}", msg),
+                result => panic!("{:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn rank_only() -> AOCResult<()> {
+        let mut hands = read_part1("32T3K\nT55J5\nKK677\nKTJJT\nQQQJA\n")?;
+        assert!(hands.iter().all(|h| h.bid == 0));
+
+        let ranked: Vec<_> = super::rank_only(&mut hands).iter().map(|h| h.hand).collect();
+        let expected: Vec<_> = read_part1("32T3K\nKTJJT\nKK677\nT55J5\nQQQJA\n")?
+            .iter()
+            .map(|h| h.hand)
+            .collect();
+        assert_eq!(ranked, expected);
+
+        // Bids are still parsed where present
+        let hands = read_part1("32T3K 765\nT55J5\n")?;
+        assert_eq!((hands[0].bid, hands[1].bid), (765, 0));
+
+        for input in ["32T3K x\n", "32T3K 1 2\n"] {
+            assert!(
+                matches!(read_part1(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn same_strength() -> AOCResult<()> {
+        let hands = read_part1("KK677 28\nKK677 1\nKTJJT 220\nT55J5 684\nQQQJA 483\n")?;
+
+        // Identical cards with different bids
+        assert!(hands[0].same_strength(&hands[1]));
+        assert_eq!(hands[0], hands[1]);
+        assert_eq!(hands[0].strength_cmp(&hands[1]), Ordering::Equal);
+
+        // Same type, but different cards
+        assert!(!hands[0].same_strength(&hands[2]));
+        assert_ne!(hands[0], hands[2]);
+        assert_eq!(hands[0].strength_cmp(&hands[2]), Ordering::Greater);
+
+        // Different type
+        assert_eq!(hands[3].strength_cmp(&hands[0]), Ordering::Greater);
+
+        // With jokers, KTJJT becomes the strongest
+        let hands = read_part2("KK677 28\nKTJJT 220\nT55J5 684\nQQQJA 483\nKTJJT 1\n")?;
+        assert!(hands[1].same_strength(&hands[4]));
+        assert!(hands.iter().all(|h| h.strength_cmp(&hands[1]) != Ordering::Greater));
+        assert!(!hands[2].same_strength(&hands[3]));
+        assert_eq!(hands[2].strength_cmp(&hands[3]), Ordering::Less);
+
+        Ok(())
+    }
+
+    // Random hands, most of them drawn from only a few cards such that all
+    // hand types are well represented
+    fn random_hands(n: usize, seed: u64) -> Vec<[Card; 5]> {
+        let all: Vec<Card> = "23456789TJQKA".chars().map(|c| Card::try_from(c).unwrap()).collect();
+        let pools = [&all[..], &[Card::Two, Card::Jack, Card::Ace], &[Card::Jack, Card::Ace]];
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n)
+            .map(|i| {
+                let pool = pools[i % pools.len()];
+                let mut hand = [Card::Two; 5];
+                hand.iter_mut().for_each(|c| *c = pool[rng.gen_range(0..pool.len())]);
+                hand
+            })
+            .collect()
+    }
+
+    // Agreeing with a reference key on all pairs also implies transitivity
+    #[test]
+    fn random_ordering() {
+        let cards = random_hands(150, 7);
+
+        let mut hands: Vec<_> = cards.iter().map(|&hand| Hand { bid: 0, hand }).collect();
+        hands.sort();
+        let reference = |h: &Hand| (typ_nested(&h.hand, None), ranks(&h.hand, false));
+        for a in &hands {
+            for b in &hands {
+                assert_eq!(a.cmp(b), reference(a).cmp(&reference(b)), "{:?} {:?}", a, b);
+                assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{:?} {:?}", a, b);
+            }
+        }
+        assert!(hands.windows(2).all(|w| reference(&w[0]) <= reference(&w[1])));
+
+        let mut hands: Vec<_> = cards.iter().map(|&hand| HandWithJokers { bid: 0, hand }).collect();
+        hands.sort();
+        let reference = |h: &HandWithJokers| {
+            (typ_nested(&h.hand, Some(Card::Jack)), ranks(&h.hand, true))
+        };
+        for a in &hands {
+            for b in &hands {
+                assert_eq!(a.cmp(b), reference(a).cmp(&reference(b)), "{:?} {:?}", a, b);
+                assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{:?} {:?}", a, b);
+            }
+        }
+        assert!(hands.windows(2).all(|w| reference(&w[0]) <= reference(&w[1])));
+
+        // All types occur
+        let types: BTreeSet<_> = cards.iter().map(|hand| typ(hand, None)).collect();
+        assert_eq!(types.len(), 7);
+    }
+
+    #[test]
+    fn card_order() -> AOCResult<()> {
+        let cards = "23456789TJQKA"
+            .chars()
+            .map(Card::try_from)
+            .collect::<AOCResult<Vec<_>>>()?;
+
+        let ranks: Vec<_> = cards.iter().map(|c| c.rank(false)).collect();
+        assert_eq!(ranks, (2..=14).collect::<Vec<_>>());
+
+        // With jokers, J is below all other cards, which keep their order
+        let mut by_rank = cards.clone();
+        by_rank.sort_by_key(|c| c.rank(true));
+        assert_eq!(by_rank[0], Card::Jack);
+        assert_eq!(
+            by_rank[1..],
+            cards.iter().copied().filter(|c| *c != Card::Jack).collect::<Vec<_>>()[..],
+        );
+
+        assert!(matches!(Card::try_from('1'), Err(AOCError::ParseError { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn typ_nested_identical() {
+        // All hands from a small set of cards, such that all hand types occur
+        // with and without wildcards
+        let cards = [1, 2, 3, 11, 14];
+        let mut count = 0;
+        for i in 0..cards.len().pow(5) {
+            let mut hand = [0u8; 5];
+            let mut i = i;
+            for c in hand.iter_mut() {
+                *c = cards[i % cards.len()];
+                i /= cards.len();
+            }
+
+            assert_eq!(typ(&hand, None), typ_nested(&hand, None), "{:?}", hand);
+            assert_eq!(typ(&hand, Some(1)), typ_nested(&hand, Some(1)), "{:?}", hand);
+            count += 1;
+        }
+        assert_eq!(count, 3125);
+    }
+
+    #[test]
+    fn classify_all() -> AOCResult<()> {
+        let input = load_input("data/test1.txt")?;
+
+        let classes = super::classify_all(&read_part1(&input)?);
+        assert_eq!(
+            classes,
+            vec![
+                (HandType::OnePair, [3, 2, 10, 3, 13], 765),
+                (HandType::ThreeOfAKind, [10, 5, 5, 11, 5], 684),
+                (HandType::TwoPair, [13, 13, 6, 7, 7], 28),
+                (HandType::TwoPair, [13, 10, 11, 11, 10], 220),
+                (HandType::ThreeOfAKind, [12, 12, 12, 11, 14], 483),
+            ]
+        );
+
+        let classes = classify_all_jokers(&read_part2(&input)?);
+        assert_eq!(classes[1].1, [10, 5, 5, 1, 5]);
+        assert_eq!(
+            classes.into_iter().map(|(typ, _, _)| typ).collect::<Vec<_>>(),
+            vec![
+                HandType::OnePair,
+                HandType::FourOfAKind,
+                HandType::TwoPair,
+                HandType::FourOfAKind,
+                HandType::FourOfAKind,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn type_histogram() -> AOCResult<()> {
+        let input = load_input("data/test1.txt")?;
+
+        assert_eq!(
+            super::type_histogram(&read_part1(&input)?),
+            BTreeMap::from([
+                (HandType::OnePair, 1),
+                (HandType::TwoPair, 2),
+                (HandType::ThreeOfAKind, 2),
+            ])
+        );
+        assert_eq!(
+            type_histogram_jokers(&read_part2(&input)?),
+            BTreeMap::from([
+                (HandType::OnePair, 1),
+                (HandType::TwoPair, 1),
+                (HandType::FourOfAKind, 3),
+            ])
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day08::{part2_brute_force, part2_brute_force_by_name, read_part1};
+
+mod common;
+use common::ghost_loops;
+
+fn brute_force(c: &mut Criterion) {
+    let data = read_part1(&ghost_loops(&[101, 103, 107])).unwrap();
+
+    let mut group = c.benchmark_group("brute_force");
+    group.sample_size(10);
+    group.bench_function("by name", |b| b.iter(|| part2_brute_force_by_name(&data, None)));
+    group.bench_function("indexed", |b| b.iter(|| part2_brute_force(&data, None)));
+    group.finish();
+}
+
+criterion_group!(benches, brute_force);
+criterion_main!(benches);
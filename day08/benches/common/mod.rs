@@ -0,0 +1,21 @@
+// Input with ghosts running in loops of the given (coprime) lengths, such that
+// they meet on end nodes only after the product of the lengths. Shared by the
+// benchmarks and the unit tests.
+pub fn ghost_loops(lens: &[usize]) -> String {
+    let mut input = "LR\n\n".to_owned();
+    for (ghost, len) in lens.iter().copied().enumerate() {
+        let node = |i: usize| {
+            let suffix = match i {
+                0 => 'A',
+                i if i == len => 'Z',
+                _ => 'X',
+            };
+            format!("{}{:04}{}", ghost, i, suffix)
+        };
+        for i in 0..=len {
+            let next = node(if i == len { 1 } else { i + 1 });
+            input.push_str(&format!("{} = ({}, {})\n", node(i), next, next));
+        }
+    }
+    input
+}
@@ -0,0 +1,764 @@
+use aoc_common::parse::split_blocks;
+use aoc_common::{AOCError, AOCResult};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use tracing::trace;
+
+// Safety limit for walking the network, should be plenty for real inputs
+pub const MAX_STEPS: Option<u64> = Some(1_000_000_000);
+
+pub fn check_steps(steps: u64, max_steps: Option<u64>) -> AOCResult<()> {
+    match max_steps {
+        Some(max_steps) if steps >= max_steps => Err(AOCError::NoSolution {
+            msg: format!("end not reached within {} steps", max_steps).into(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+pub struct Data {
+    path: String,
+    network: HashMap<String, (String, String)>,
+}
+
+pub fn read_part1(input: &str) -> AOCResult<Data> {
+    // `split_blocks` skips leading blank lines, so check for a missing path
+    // here to get a clearer message than the section count below
+    if input.lines().next().is_none_or(|l| l.trim().is_empty()) {
+        return Err(AOCError::ParseError {
+            msg: "empty path".into(),
+        });
+    }
+
+    // The path, then the network, separated by a blank line
+    let (path, network) = match split_blocks(input)[..] {
+        [path, network] => (path, network),
+        ref blocks => {
+            return Err(AOCError::ParseError {
+                msg: format!("expected path and network, found {} sections", blocks.len()).into(),
+            })
+        }
+    };
+
+    let path = path.trim();
+    if path.lines().count() != 1 {
+        return Err(AOCError::ParseError {
+            msg: format!("invalid path: '{}'", path).into(),
+        });
+    }
+    if let Some(dir) = path.chars().find(|dir| !matches!(dir, 'L' | 'R')) {
+        return Err(AOCError::ParseError {
+            msg: format!("invalid direction '{}' in path '{}'", dir, path).into(),
+        });
+    }
+    let path = path.to_owned();
+
+    let network = network
+        .lines()
+        .map(|l| {
+            let parse_error = || AOCError::ParseError {
+                msg: format!("invalid node: '{}'", l).into(),
+            };
+
+            let (from, to) = l.split_once(" = ").ok_or_else(parse_error)?;
+            let (to_left, to_right) = to
+                .trim()
+                .strip_prefix('(')
+                .and_then(|to| to.strip_suffix(')'))
+                .and_then(|to| to.split_once(','))
+                .ok_or_else(parse_error)?;
+            let (from, to_left, to_right) = (from.trim(), to_left.trim(), to_right.trim());
+            if from.is_empty() || to_left.is_empty() || to_right.is_empty() {
+                return Err(parse_error());
+            }
+
+            Ok((
+                from.to_owned(),
+                (to_left.to_owned(), to_right.to_owned()),
+            ))
+        })
+        .collect::<AOCResult<HashMap<_, _>>>()?;
+
+    // Such that walking the network never gets stuck, and it can be indexed
+    for (from, (to_left, to_right)) in &network {
+        for to in [to_left, to_right] {
+            if !network.contains_key(to) {
+                return Err(AOCError::ParseError {
+                    msg: format!("unknown node '{}' referenced by '{}'", to, from).into(),
+                });
+            }
+        }
+    }
+
+    Ok(Data { path, network })
+}
+
+// The node reached from `loc` by going in direction `dir`. `read_part1`
+// ensures that all referenced nodes exist, so this only fails for an unknown
+// start node.
+fn next_node<'a>(data: &'a Data, loc: &str, dir: Option<char>) -> AOCResult<&'a str> {
+    let (next_left, next_right) = data.network.get(loc).ok_or_else(|| AOCError::NoSolution {
+        msg: format!("node '{}' is not in the network", loc).into(),
+    })?;
+    match dir {
+        Some('L') => Ok(next_left),
+        Some('R') => Ok(next_right),
+        _ => Err(AOCError::ParseError {
+            msg: format!("invalid direction {:?}", dir).into(),
+        }),
+    }
+}
+
+pub fn part1(data: &Data) -> AOCResult<u64> {
+    part1_with_limit(data, MAX_STEPS)
+}
+
+pub fn part1_with_limit(data: &Data, max_steps: Option<u64>) -> AOCResult<u64> {
+    let mut loc = "AAA";
+    let mut steps = 0;
+    let mut dirs = data.path.chars().cycle();
+    while loc != "ZZZ" {
+        check_steps(steps, max_steps)?;
+        loc = next_node(data, loc, dirs.next())?;
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+// Longest path that `trace` is willing to store
+pub const MAX_TRACE_LEN: usize = 1_000_000;
+
+// All nodes visited on the way from `start` to the first end node, both
+// included
+pub fn trace(data: &Data, start: &str, is_end: impl Fn(&str) -> bool) -> AOCResult<Vec<String>> {
+    let mut loc = start;
+    let mut visited = vec![loc.to_owned()];
+    let mut dirs = data.path.chars().cycle();
+    while !is_end(loc) {
+        check_steps(visited.len() as u64 - 1, MAX_STEPS)?;
+        if visited.len() >= MAX_TRACE_LEN {
+            return Err(AOCError::NoSolution {
+                msg: format!("end not reached within a trace of {} nodes", MAX_TRACE_LEN).into(),
+            });
+        }
+        loc = next_node(data, loc, dirs.next())?;
+        visited.push(loc.to_owned());
+    }
+    Ok(visited)
+}
+
+// The network as a Graphviz digraph, with edges labeled by direction. Nodes
+// are sorted by name for a stable output.
+pub fn to_dot(data: &Data) -> String {
+    let mut nodes: Vec<_> = data.network.iter().collect();
+    nodes.sort_unstable();
+
+    let mut dot = "digraph network {\n".to_owned();
+    for (from, (to_left, to_right)) in nodes {
+        for (dir, to) in [('L', to_left), ('R', to_right)] {
+            dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"{}\"];\n", from, to, dir));
+        }
+    }
+    dot.push_str("}\n");
+
+    dot
+}
+
+// The network with nodes referred to by index rather than by name, which
+// avoids hashing strings in every step
+pub struct IndexedNetwork {
+    // Per node, the next node for L and R
+    next: Vec<[usize; 2]>,
+    is_end: Vec<bool>,
+    starts: Vec<usize>,
+    // 0 for L, 1 for R
+    path: Vec<usize>,
+}
+
+impl IndexedNetwork {
+    pub fn new(data: &Data) -> AOCResult<Self> {
+        let mut names: Vec<&str> = data.network.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        let index: HashMap<&str, usize> = (0..).zip(names.iter().copied()).map(|(i, n)| (n, i)).collect();
+
+        let lookup = |name: &str| {
+            index.get(name).copied().ok_or_else(|| AOCError::ParseError {
+                msg: format!("unknown node '{}'", name).into(),
+            })
+        };
+        let next = names
+            .iter()
+            .map(|name| {
+                let (left, right) = &data.network[*name];
+                Ok([lookup(left)?, lookup(right)?])
+            })
+            .collect::<AOCResult<_>>()?;
+        let path = data
+            .path
+            .chars()
+            .map(|dir| match dir {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(AOCError::ParseError {
+                    msg: format!("invalid direction '{}'", dir).into(),
+                }),
+            })
+            .collect::<AOCResult<_>>()?;
+
+        Ok(Self {
+            next,
+            is_end: names.iter().map(|n| is_ghost_end(n)).collect(),
+            starts: (0..names.len()).filter(|i| is_ghost_start(names[*i])).collect(),
+            path,
+        })
+    }
+}
+
+// Part 2 needs at least one ghost, and somewhere for it to go
+fn check_ghost_nodes(data: &Data) -> AOCResult<()> {
+    for (kind, is_kind) in [("start", is_ghost_start as fn(&str) -> bool), ("end", is_ghost_end)] {
+        if !data.network.keys().any(|node| is_kind(node)) {
+            return Err(AOCError::ParseError {
+                msg: format!("no {} nodes for part 2", kind).into(),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Refuse to brute force if, assuming that the LCM shortcut applies, it'd take
+// more than `max_steps`
+fn check_brute_force_feasible(data: &Data, max_steps: Option<u64>) -> AOCResult<()> {
+    check_ghost_nodes(data)?;
+    if let Some(max_steps) = max_steps {
+        // This is the actual result then
+        let estimate = match lcm_steps(data, is_ghost_start, is_ghost_end, MAX_STEPS) {
+            Ok(estimate) => estimate,
+            Err(AOCError::Overflow) => {
+                return Err(AOCError::NoSolution {
+                    msg: "brute force would take more than u64::MAX steps".into(),
+                })
+            }
+            Err(e) => return Err(e),
+        };
+        if estimate > max_steps {
+            return Err(AOCError::NoSolution {
+                msg: format!(
+                    "brute force would take about {} steps, more than the limit of {}; \
+                    use the LCM-based solution instead",
+                    estimate, max_steps,
+                ).into(),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Takes forever on the real input
+pub fn part2_brute_force(data: &Data, max_steps: Option<u64>) -> AOCResult<u64> {
+    check_brute_force_feasible(data, max_steps)?;
+
+    let network = IndexedNetwork::new(data)?;
+    let mut locs = network.starts.clone();
+    let mut steps = 0;
+    let mut dirs = network.path.iter().cycle();
+    trace!(?locs);
+    while locs.iter().any(|loc| !network.is_end[*loc]) {
+        check_steps(steps, max_steps)?;
+        let dir = *dirs.next().ok_or(AOCError::ParseError { msg: "empty path".into() })?;
+        locs.iter_mut().for_each(|loc| *loc = network.next[*loc][dir]);
+        trace!(steps, ?locs);
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+// Same as `part2_brute_force`, but looking up nodes by name; kept around to
+// benchmark against
+pub fn part2_brute_force_by_name(data: &Data, max_steps: Option<u64>) -> AOCResult<u64> {
+    check_brute_force_feasible(data, max_steps)?;
+
+    let mut locs: Vec<&str> = data
+        .network
+        .keys()
+        .map(String::as_str)
+        .filter(|node| is_ghost_start(node))
+        .collect();
+    let mut steps = 0;
+    let mut dirs = data.path.chars().cycle();
+    while locs.iter().any(|node| !is_ghost_end(node)) {
+        check_steps(steps, max_steps)?;
+        let dir = dirs.next();
+        for loc in locs.iter_mut() {
+            *loc = next_node(data, loc, dir)?;
+        }
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+// The nodes that ghosts start from and have to reach in part 2
+pub fn is_ghost_start(node: &str) -> bool {
+    node.ends_with('A')
+}
+
+pub fn is_ghost_end(node: &str) -> bool {
+    node.ends_with('Z')
+}
+
+// Periodic behaviour of a single ghost: After `start` steps, it enters a
+// cycle of `len` steps. `ends` are all steps before `start + len` at which
+// the ghost is on an end node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    start: u64,
+    len: u64,
+    ends: Vec<u64>,
+}
+
+pub fn find_cycle(
+    data: &Data,
+    start: &str,
+    is_end: impl Fn(&str) -> bool,
+    max_steps: Option<u64>,
+) -> AOCResult<Cycle> {
+    let path: Vec<char> = data.path.chars().collect();
+    let mut loc = start;
+
+    // first encounter of each (node, position in the path)
+    let mut history: HashMap<(&str, usize), u64> = HashMap::new();
+    let mut ends = Vec::new();
+
+    let mut step = 0;
+    loop {
+        let idx = (step % path.len() as u64) as usize;
+        match history.entry((loc, idx)) {
+            Entry::Occupied(prev_encounter) => {
+                let cycle_start = *prev_encounter.get();
+                return Ok(Cycle {
+                    start: cycle_start,
+                    len: step - cycle_start,
+                    ends,
+                });
+            },
+            Entry::Vacant(new) => { new.insert(step); }
+        };
+
+        if is_end(loc) {
+            ends.push(step);
+        }
+
+        check_steps(step, max_steps)?;
+        loc = next_node(data, loc, Some(path[idx]))?;
+        step += 1;
+    }
+}
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+pub fn lcm(a: u64, b: u64) -> AOCResult<u64> {
+    (a / gcd(a, b)).checked_mul(b).ok_or(AOCError::Overflow)
+}
+
+// This relies on each ghost reaching an end node exactly at the end of its
+// cycle (which is how the inputs are constructed), in which case it is at an
+// end node after any multiple of its cycle length.
+pub fn lcm_steps(
+    data: &Data,
+    is_start: impl Fn(&str) -> bool,
+    is_end: impl Fn(&str) -> bool,
+    max_steps: Option<u64>,
+) -> AOCResult<u64> {
+    let mut steps = 1;
+    for start in data.network.keys().filter(|node| is_start(node)) {
+        let cycle = find_cycle(data, start, &is_end, max_steps)?;
+        let first_end = *cycle.ends.first().ok_or_else(|| AOCError::NoSolution {
+            msg: format!("ghost starting at {} never reaches an end node", start).into(),
+        })?;
+        steps = lcm(steps, first_end)?;
+    }
+
+    Ok(steps)
+}
+
+// Check that `lcm_steps` is valid, i.e. that each ghost is on an end node
+// after exactly the multiples of its first end step. That is the case if the
+// first end lies within the cycle, the cycle length is a multiple of it, and
+// there are no other end nodes in between. The cycle is over (node, position
+// in the path), so it may contain several ends if the node sequence repeats
+// more often than the path.
+pub fn verify_lcm_assumptions(
+    data: &Data,
+    is_start: impl Fn(&str) -> bool,
+    is_end: impl Fn(&str) -> bool,
+) -> AOCResult<()> {
+    for start in data.network.keys().filter(|node| is_start(node)) {
+        let cycle = find_cycle(data, start, &is_end, MAX_STEPS)?;
+        let violation = |reason: &str| AOCError::NoSolution {
+            msg: format!(
+                "LCM shortcut doesn't apply to ghost starting at {}: {}; \
+                use the Chinese remainder theorem instead",
+                start, reason,
+            ).into(),
+        };
+
+        let first_end = *cycle.ends.first().ok_or_else(|| violation("no end node"))?;
+        if first_end == 0 {
+            return Err(violation("starts on an end node"));
+        }
+        // Then, the ghost would be back on the (non-end) start node after a
+        // multiple of the first end step
+        if cycle.start == 0 {
+            return Err(violation("start node is part of the cycle"));
+        }
+        if first_end < cycle.start {
+            return Err(violation("end node before the cycle"));
+        }
+        if cycle.len % first_end != 0 {
+            return Err(violation("cycle length isn't a multiple of the first end step"));
+        }
+        let expected_ends: Vec<_> = (1..)
+            .map(|k| k * first_end)
+            .take_while(|step| *step < cycle.start + cycle.len)
+            .collect();
+        if cycle.ends != expected_ends {
+            return Err(violation("end nodes aren't evenly spaced"));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn part2(data: &Data) -> AOCResult<u64> {
+    check_ghost_nodes(data)?;
+    verify_lcm_assumptions(data, is_ghost_start, is_ghost_end)?;
+    lcm_steps(data, is_ghost_start, is_ghost_end, MAX_STEPS)
+}
+
+// Fixtures shared with the benchmarks
+#[cfg(test)]
+#[path = "../benches/common/mod.rs"]
+mod common;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::load_input;
+    use common::ghost_loops;
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $read_data:path,
+            $compute:path,
+            $expected:expr
+            $(,)?  // allow (optional) trailing comma
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                let input = load_input($datapath)?;
+                match $compute(&mut $read_data(&input)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(part11, "data/test1.txt", read_part1, super::part1, 2);
+    aoc_test!(part12, "data/test2.txt", read_part1, super::part1, 6);
+    aoc_test!(part2, "data/test3.txt", read_part1, super::part2, 6);
+
+    #[test]
+    fn part2_brute_force() -> AOCResult<()> {
+        let data = read_part1(&load_input("data/test3.txt")?)?;
+        assert_eq!(super::part2_brute_force(&data, MAX_STEPS)?, 6);
+        assert_eq!(part2_brute_force_by_name(&data, MAX_STEPS)?, 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn verify_lcm_assumptions() -> AOCResult<()> {
+        let data = read_part1(&load_input("data/test3.txt")?)?;
+        super::verify_lcm_assumptions(&data, is_ghost_start, is_ghost_end)?;
+
+        // Reaches an end node after 1, 4, 7, ... steps
+        let data = read_part1("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (BBB, BBB)\nBBB = (CCC, CCC)\nCCC = (ZZZ, ZZZ)\n")?;
+        assert!(matches!(super::verify_lcm_assumptions(&data, is_ghost_start, is_ghost_end), Err(AOCError::NoSolution { .. })));
+        assert!(matches!(super::part2(&data), Err(AOCError::NoSolution { .. })));
+
+        // Never reaches an end node
+        let data = read_part1("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n")?;
+        assert!(matches!(super::verify_lcm_assumptions(&data, is_ghost_start, is_ghost_end), Err(AOCError::NoSolution { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_predicates() -> AOCResult<()> {
+        // Two ghosts starting at S*, reaching E* after 2 and 3 steps
+        let data = read_part1(
+            "L\n\nS1 = (X1, X1)\nX1 = (E1, E1)\nE1 = (X1, X1)\n\
+             S2 = (Y1, Y1)\nY1 = (Y2, Y2)\nY2 = (E2, E2)\nE2 = (Y1, Y1)\n",
+        )?;
+        let is_start = |n: &str| n.starts_with('S');
+        let is_end = |n: &str| n.starts_with('E');
+        super::verify_lcm_assumptions(&data, is_start, is_end)?;
+        assert_eq!(lcm_steps(&data, is_start, is_end, MAX_STEPS)?, 6);
+
+        // Only the first ghost, and reaching X1 after 1, 3, 5, ... steps
+        let is_start = |n: &str| n == "S1";
+        assert_eq!(lcm_steps(&data, is_start, |n| n == "E1", MAX_STEPS)?, 2);
+        assert!(matches!(
+            super::verify_lcm_assumptions(&data, is_start, |n| n == "X1"),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        // Bouncing between a start and an end node
+        let data = read_part1("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (AAA, AAA)\n")?;
+        assert!(matches!(
+            super::verify_lcm_assumptions(&data, is_ghost_start, is_ghost_end),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn node_whitespace() -> AOCResult<()> {
+        let data = read_part1("L\n\nAAA  =   ( ZZZ ,  BBB)\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n")?;
+        assert_eq!(data.network["AAA"], ("ZZZ".to_owned(), "BBB".to_owned()));
+        assert_eq!(super::part1(&data)?, 1);
+
+        assert!(matches!(
+            read_part1("L\n\nAAA = (, BBB)\n"),
+            Err(AOCError::ParseError { .. })
+        ));
+        assert!(matches!(
+            read_part1("L\n\nAAA = BBB, CCC\n"),
+            Err(AOCError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_dot() -> AOCResult<()> {
+        let data = read_part1(&load_input("data/test2.txt")?)?;
+        assert_eq!(
+            super::to_dot(&data),
+            "digraph network {\n    \
+             \"AAA\" -> \"BBB\" [label=\"L\"];\n    \
+             \"AAA\" -> \"BBB\" [label=\"R\"];\n    \
+             \"BBB\" -> \"AAA\" [label=\"L\"];\n    \
+             \"BBB\" -> \"ZZZ\" [label=\"R\"];\n    \
+             \"ZZZ\" -> \"ZZZ\" [label=\"L\"];\n    \
+             \"ZZZ\" -> \"ZZZ\" [label=\"R\"];\n\
+             }\n"
+        );
+
+        let data = read_part1(&load_input("data/test1.txt")?)?;
+        let dot = super::to_dot(&data);
+        assert!(dot.contains("\"AAA\" -> \"CCC\" [label=\"R\"];"));
+        assert!(dot.contains("\"CCC\" -> \"ZZZ\" [label=\"L\"];"));
+        assert_eq!(dot.lines().filter(|l| l.contains("->")).count(), 2 * 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dangling_target() {
+        match read_part1("L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n") {
+            Err(AOCError::ParseError { msg }) => {
+                assert_eq!(msg, "unknown node 'BBB' referenced by 'AAA'");
+            }
+            result => panic!("{:?}", result.map(|data| data.network)),
+        }
+    }
+
+    #[test]
+    fn missing_ghost_nodes() -> AOCResult<()> {
+        // Parses fine, but there's nowhere for ghosts to go
+        let data = read_part1("L\n\n11A = (ZZY, ZZY)\nZZY = (11A, 11A)\n")?;
+        for result in [
+            super::part2(&data),
+            super::part2_brute_force(&data, MAX_STEPS),
+            part2_brute_force_by_name(&data, MAX_STEPS),
+        ] {
+            match result {
+                Err(AOCError::ParseError { msg }) => assert_eq!(msg, "no end nodes for part 2"),
+                result => panic!("{:?}", result),
+            }
+        }
+
+        let data = read_part1("L\n\nZZZ = (ZZZ, ZZZ)\n")?;
+        assert!(matches!(super::part2(&data), Err(AOCError::ParseError { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn trace() -> AOCResult<()> {
+        let data = read_part1(&load_input("data/test1.txt")?)?;
+        assert_eq!(super::trace(&data, "AAA", |n| n == "ZZZ")?, vec!["AAA", "CCC", "ZZZ"]);
+        assert_eq!(super::trace(&data, "ZZZ", |n| n == "ZZZ")?, vec!["ZZZ"]);
+
+        let data = read_part1(&load_input("data/test2.txt")?)?;
+        let trace = super::trace(&data, "AAA", |n| n == "ZZZ")?;
+        assert_eq!(trace, vec!["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"]);
+        assert_eq!(trace.len() as u64 - 1, super::part1(&data)?);
+
+        // Never reaches the end
+        assert!(matches!(
+            super::trace(&data, "AAA", |_| false),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sections() -> AOCResult<()> {
+        // Trailing blank lines after the network
+        let data = read_part1("RL\n\nAAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)\n\n\n  \n")?;
+        assert_eq!(data.path, "RL");
+        assert_eq!(data.network.len(), 2);
+        assert_eq!(super::part1(&data)?, 2);
+
+        for input in [
+            "",
+            "RL\n",
+            "RL\nAAA = (ZZZ, AAA)\n",
+            "RL\n\nAAA = (ZZZ, AAA)\n\nZZZ = (ZZZ, ZZZ)\n",
+            "R\nL\n\nAAA = (ZZZ, AAA)\n",
+            "RXL\n\nAAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)\n",
+            "rl\n\nAAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)\n",
+        ] {
+            assert!(
+                matches!(read_part1(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_path() {
+        for input in ["\n\nAAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)\n", "  \n\nAAA = (ZZZ, AAA)\n", "\n"] {
+            match read_part1(input) {
+                Err(AOCError::ParseError { msg }) => assert_eq!(msg, "empty path"),
+                Err(e) => panic!("{:?}", e),
+                Ok(_) => panic!("{:?}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn find_cycle() -> AOCResult<()> {
+        let data = read_part1(&load_input("data/test3.txt")?)?;
+        assert_eq!(
+            super::find_cycle(&data, "11A", is_ghost_end, MAX_STEPS)?,
+            Cycle { start: 1, len: 2, ends: vec![2] },
+        );
+        assert_eq!(
+            super::find_cycle(&data, "22A", is_ghost_end, MAX_STEPS)?,
+            Cycle { start: 1, len: 6, ends: vec![3, 6] },
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn end_mid_path() -> AOCResult<()> {
+        // The node loops (2 and 3 steps) are shorter than the path, and end
+        // nodes are reached in the middle of a pass over the path
+        let data = read_part1(
+            "LRRLL\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+             22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\n",
+        )?;
+        assert_eq!(
+            super::find_cycle(&data, "11A", is_ghost_end, MAX_STEPS)?,
+            Cycle { start: 1, len: 10, ends: vec![2, 4, 6, 8, 10] },
+        );
+        assert_eq!(
+            super::find_cycle(&data, "22A", is_ghost_end, MAX_STEPS)?,
+            Cycle { start: 1, len: 15, ends: vec![3, 6, 9, 12, 15] },
+        );
+        assert_eq!(super::part2(&data)?, 6);
+        assert_eq!(super::part2_brute_force(&data, MAX_STEPS)?, 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_brute_force_feasibility() -> AOCResult<()> {
+        let data = read_part1(&ghost_loops(&[7, 11]))?;
+        assert_eq!(super::part2_brute_force(&data, Some(100_000))?, 77);
+        assert_eq!(part2_brute_force_by_name(&data, Some(100_000))?, 77);
+        assert_eq!(super::part2(&data)?, 77);
+
+        let data = read_part1(&ghost_loops(&[1009, 1013]))?;
+        match super::part2_brute_force(&data, Some(100_000)) {
+            Err(AOCError::NoSolution { msg }) => assert!(msg.contains("LCM")),
+            result => panic!("unexpected result: {:?}", result),
+        }
+        assert_eq!(super::part2(&data)?, 1009 * 1013);
+
+        Ok(())
+    }
+
+    #[test]
+    fn lcm_overflow() -> AOCResult<()> {
+        assert_eq!(super::lcm(4, 6)?, 12);
+        assert_eq!(super::lcm(1 << 62, 2)?, 1 << 62);
+        assert!(matches!(super::lcm(1 << 63, 3), Err(AOCError::Overflow)));
+
+        // Coprime cycle lengths whose product exceeds u64::MAX
+        let data = read_part1(&ghost_loops(&[8191, 8209, 8219, 8221, 8231]))?;
+        assert!(matches!(
+            lcm_steps(&data, is_ghost_start, is_ghost_end, MAX_STEPS),
+            Err(AOCError::Overflow)
+        ));
+        assert!(matches!(super::part2(&data), Err(AOCError::Overflow)));
+        match super::part2_brute_force(&data, Some(100_000)) {
+            Err(AOCError::NoSolution { msg }) => assert!(msg.contains("u64::MAX"), "{}", msg),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable_end() -> AOCResult<()> {
+        // ZZZ is not reachable from AAA
+        let data = read_part1("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n")?;
+        assert!(matches!(
+            part1_with_limit(&data, Some(1000)),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        // No AAA at all
+        let data = read_part1(&load_input("data/test3.txt")?)?;
+        assert!(matches!(super::part1(&data), Err(AOCError::NoSolution { .. })));
+        assert!(matches!(
+            super::trace(&data, "AAA", |node| node == "ZZZ"),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        assert!(matches!(
+            super::part2_brute_force(&data, Some(5)),
+            Err(AOCError::NoSolution { .. })
+        ));
+
+        Ok(())
+    }
+}
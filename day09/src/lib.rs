@@ -0,0 +1,173 @@
+use aoc_common::seq::{extrapolate_iterative, StopCondition};
+use aoc_common::{AOCError, AOCResult};
+
+fn parse_number(num: &str, line: usize) -> AOCResult<i64> {
+    num.parse().map_err(|_| {
+        let msg = if num.contains('.') && num.parse::<f64>().is_ok() {
+            format!("expected integer, found float `{}` on line {}", num, line)
+        } else {
+            format!("invalid number: '{}'", num)
+        };
+        AOCError::ParseError { msg: msg.into() }
+    })
+}
+
+// Numbers may be separated by whitespace and/or commas
+pub fn read_part1(input: &str) -> AOCResult<Vec<Vec<i64>>> {
+    input.lines()
+        .enumerate()
+        .map(|(i, l)| {
+            l.split(|c: char| c.is_ascii_whitespace() || c == ',')
+                .filter(|num| !num.is_empty())
+                .map(|num| parse_number(num, i + 1))
+                .collect()
+        })
+        .collect()
+}
+
+fn checked_total(extrapolations: &[i64]) -> AOCResult<i64> {
+    extrapolations
+        .iter()
+        .try_fold(0i64, |acc, x| acc.checked_add(*x))
+        .ok_or(AOCError::Overflow)
+}
+
+// The (front, back) extrapolation of each sequence
+pub fn extrapolate_all(data: &[Vec<i64>]) -> AOCResult<Vec<(i64, i64)>> {
+    data.iter()
+        .map(|x| extrapolate_iterative(x, StopCondition::default()))
+        .collect()
+}
+
+pub fn part1(data: &[Vec<i64>]) -> AOCResult<(i64, Vec<i64>)> {
+    let extrapolations: Vec<_> = extrapolate_all(data)?.into_iter().map(|(_, back)| back).collect();
+
+    let total = checked_total(&extrapolations)?;
+    Ok((total, extrapolations))
+}
+
+// Like `part1`, but summing the absolute values of the extrapolations
+pub fn part1_abs(data: &[Vec<i64>]) -> AOCResult<i64> {
+    let extrapolations = extrapolate_all(data)?
+        .into_iter()
+        .map(|(_, back)| back.checked_abs().ok_or(AOCError::Overflow))
+        .collect::<AOCResult<Vec<_>>>()?;
+
+    checked_total(&extrapolations)
+}
+
+pub fn part2(data: &[Vec<i64>]) -> AOCResult<(i64, Vec<i64>)> {
+    let extrapolations: Vec<_> = extrapolate_all(data)?.into_iter().map(|(front, _)| front).collect();
+
+    let total = checked_total(&extrapolations)?;
+    Ok((total, extrapolations))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::load_input;
+    use aoc_common::seq::levels_to_base;
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $read_data:path,
+            $compute:path,
+            $expected:expr
+            $(,)?  // allow (optional) trailing comma
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                let input = load_input($datapath)?;
+                match $compute(&mut $read_data(&input)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    aoc_test!(part1, "data/test1.txt", read_part1, super::part1, (114, vec![18, 28, 68]));
+    aoc_test!(part2, "data/test1.txt", read_part1, super::part2, (2, vec![-3, 0, 5]));
+
+    #[test]
+    fn separators() -> AOCResult<()> {
+        let expected = read_part1(&load_input("data/test1.txt")?)?;
+        assert_eq!(
+            read_part1("0,3,6,9,12,15\n1, 3, 6, 10, 15, 21\n10 13,16 , 21 30 45\n")?,
+            expected
+        );
+
+        for input in ["1 2 x 4\n", "1;2;3\n", "1.5, 2\n"] {
+            assert!(
+                matches!(read_part1(input), Err(AOCError::ParseError { .. })),
+                "{:?}",
+                input
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn overflowing_total() -> AOCResult<()> {
+        // Each extrapolation fits, but not their sum
+        let data = vec![vec![i64::MAX, i64::MAX], vec![1, 1, 1]];
+        assert!(matches!(super::part1(&data), Err(AOCError::Overflow)));
+        assert!(matches!(super::part2(&data), Err(AOCError::Overflow)));
+
+        let data = vec![vec![i64::MAX, i64::MAX], vec![-1, -1]];
+        assert_eq!(super::part1(&data)?, (i64::MAX - 1, vec![i64::MAX, -1]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn part1_abs() -> AOCResult<()> {
+        let data = read_part1(&load_input("data/test1.txt")?)?;
+        assert_eq!(super::part1_abs(&data)?, 114);
+
+        // Extrapolates to -4 and 18
+        let data = vec![vec![8, 4, 0], vec![0, 3, 6, 9, 12, 15]];
+        assert_eq!(super::part1(&data)?.0, 14);
+        assert_eq!(super::part1_abs(&data)?, 22);
+
+        let data = vec![vec![i64::MIN, i64::MIN]];
+        assert!(matches!(super::part1_abs(&data), Err(AOCError::Overflow)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn levels() -> AOCResult<()> {
+        let data = read_part1(&load_input("data/test1.txt")?)?;
+        let levels = data
+            .iter()
+            .map(|x| levels_to_base(x, StopCondition::default()))
+            .collect::<AOCResult<Vec<_>>>()?;
+        assert_eq!(levels, vec![1, 2, 3]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn floats() {
+        match read_part1("1 2 3\n4 3.5 -1\n") {
+            Err(AOCError::ParseError { msg }) => {
+                assert_eq!(msg, "expected integer, found float `3.5` on line 2");
+            }
+            result => panic!("{:?}", result),
+        }
+
+        // Not numeric-looking
+        match read_part1("1 2.x 3\n") {
+            Err(AOCError::ParseError { msg }) => assert_eq!(msg, "invalid number: '2.x'"),
+            result => panic!("{:?}", result),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use aoc_common::{AOCError, AOCResult};
+
+pub fn read_part1(input: &str) -> AOCResult<Vec<String>> {
+    Ok(input.lines()
+        .map(str::to_owned)
+        .collect()
+    )
+}
+
+pub fn part1(_data: &[String]) -> AOCResult<i64> {
+    Err(AOCError::NotYetSolved)
+}
+
+pub fn part2(_data: &[String]) -> AOCResult<i64> {
+    Err(AOCError::NotYetSolved)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::load_input;
+
+    macro_rules! aoc_test {
+        (
+            $func:ident,
+            $datapath:literal,
+            $read_data:path,
+            $compute:path,
+            $expected:expr
+            $(,)?  // allow (optional) trailing comma
+        ) => {
+            #[test]
+            fn $func() -> AOCResult<()> {
+                let input = load_input($datapath)?;
+                match $compute(&mut $read_data(&input)?) {
+                    Ok(result) => assert_eq!(result, $expected),
+                    Err(AOCError::NotYetSolved) => {}
+                    Err(e) => return Err(e),
+                };
+
+                Ok(())
+            }
+        };
+    }
+
+    // When the input is parsed via `FromStr`, use this variant instead
+    // (requires `use aoc_common::FromFile;`):
+    //
+    // macro_rules! aoc_test {
+    //     (
+    //         $func:ident,
+    //         $datapath:literal,
+    //         $dtype:ty,
+    //         $compute:path,
+    //         $expected:expr
+    //         $(,)?  // allow (optional) trailing comma
+    //     ) => {
+    //         #[test]
+    //         fn $func() -> AOCResult<()> {
+    //             match $compute(&<$dtype>::from_file($datapath)?) {
+    //                 Ok(result) => assert_eq!(result, $expected),
+    //                 Err(AOCError::NotYetSolved) => {}
+    //                 Err(e) => return Err(e),
+    //             };
+    //
+    //             Ok(())
+    //         }
+    //     };
+    // }
+    //
+    // aoc_test!(part1, "data/test1.txt", Data, super::part1, 0);
+    // aoc_test!(part2, "data/test1.txt", Data, super::part2, 0);
+
+    aoc_test!(part1, "data/test1.txt", read_part1, super::part1, 0);
+    aoc_test!(part2, "data/test1.txt", read_part1, super::part2, 0);
+}
@@ -0,0 +1,197 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+enum XtaskError {
+    #[error("I/O error for {path:?}")]
+    IOError {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[error("{msg}")]
+    Usage { msg: Cow<'static, str> },
+}
+
+type XtaskResult<T> = Result<T, XtaskError>;
+
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> XtaskError + '_ {
+    move |source| XtaskError::IOError {
+        source,
+        path: path.into(),
+    }
+}
+
+const USAGE: &str = "usage: cargo xtask new-day <N>";
+
+fn copy_dir(from: &Path, to: &Path) -> XtaskResult<()> {
+    fs::create_dir(to).map_err(io_error(to))?;
+    for entry in fs::read_dir(from).map_err(io_error(from))? {
+        let entry = entry.map_err(io_error(from))?;
+        let src = entry.path();
+        let dest = to.join(entry.file_name());
+        if entry.file_name() == "target" {
+            continue;
+        }
+        if src.is_dir() {
+            copy_dir(&src, &dest)?;
+        } else {
+            fs::copy(&src, &dest).map_err(io_error(&src))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn substitute(path: &Path, from: &str, to: &str) -> XtaskResult<()> {
+    let content = fs::read_to_string(path).map_err(io_error(path))?;
+    fs::write(path, content.replace(from, to)).map_err(io_error(path))
+}
+
+// Enable the member in the workspace manifest, either by uncommenting it or
+// by appending it to the list of members
+fn add_member(manifest: &Path, name: &str) -> XtaskResult<()> {
+    let content = fs::read_to_string(manifest).map_err(io_error(manifest))?;
+    let member = format!("\"{}\",", name);
+    let commented = format!("#{}", member);
+
+    let content = if content.contains(&commented) {
+        content.replacen(&commented, &member, 1)
+    } else if content.lines().any(|l| l.trim() == member) {
+        return Ok(());
+    } else {
+        let members_start = content.find("members = [").ok_or(XtaskError::Usage {
+            msg: "workspace manifest has no members list".into(),
+        })?;
+        let members_end = members_start
+            + content[members_start..].find("\n]").ok_or(XtaskError::Usage {
+                msg: "unterminated workspace members list".into(),
+            })?;
+        let mut content = content;
+        content.insert_str(members_end, &format!("\n\t{}", member));
+        content
+    };
+
+    fs::write(manifest, content).map_err(io_error(manifest))
+}
+
+// Scaffold `dayNN` from the skeleton, returns the path of the new crate
+fn new_day(root: &Path, day: u32) -> XtaskResult<PathBuf> {
+    let name = format!("day{:02}", day);
+    let path = root.join(&name);
+    if path.exists() {
+        return Err(XtaskError::Usage {
+            msg: format!("{} already exists", path.display()).into(),
+        });
+    }
+
+    copy_dir(&root.join("skeleton"), &path)?;
+    substitute(
+        &path.join("Cargo.toml"),
+        "name = \"skeleton\"",
+        &format!("name = \"{}\"", name),
+    )?;
+    let main_rs = path.join("src").join("main.rs");
+    substitute(&main_rs, "dayXX", &name)?;
+    substitute(&main_rs, "use skeleton::", &format!("use {}::", name))?;
+
+    let data = path.join("data");
+    fs::create_dir_all(&data).map_err(io_error(&data))?;
+    for file in ["test1.txt", "test2.txt", "input.txt"] {
+        let file = data.join(file);
+        if !file.exists() {
+            fs::write(&file, "").map_err(io_error(&file))?;
+        }
+    }
+
+    add_member(&root.join("Cargo.toml"), &name)?;
+
+    Ok(path)
+}
+
+fn main() -> XtaskResult<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["new-day", day] => {
+            let day = day.parse::<u32>().map_err(|_| XtaskError::Usage {
+                msg: format!("invalid day '{}'\n{}", day, USAGE).into(),
+            })?;
+            let path = new_day(root, day)?;
+            println!("Created {}", path.display());
+        }
+        _ => {
+            return Err(XtaskError::Usage { msg: USAGE.into() });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn setup_root(name: &str) -> XtaskResult<PathBuf> {
+        let root = std::env::temp_dir().join(format!("xtask-{}-{}", name, std::process::id()));
+        if root.exists() {
+            fs::remove_dir_all(&root).map_err(io_error(&root))?;
+        }
+        fs::create_dir_all(root.join("skeleton").join("src")).map_err(io_error(&root))?;
+        fs::write(
+            root.join("skeleton").join("Cargo.toml"),
+            "[package]\nname = \"skeleton\"\n",
+        )
+        .map_err(io_error(&root))?;
+        fs::write(
+            root.join("skeleton").join("src").join("main.rs"),
+            "use skeleton::part1;\nfn main() { println!(\"dayXX\"); }\n",
+        )
+        .map_err(io_error(&root))?;
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n\t\"day01\",\n\t#\"day11\",\n]\n",
+        )
+        .map_err(io_error(&root))?;
+
+        Ok(root)
+    }
+
+    #[test]
+    fn new_day() -> XtaskResult<()> {
+        let root = setup_root("new-day")?;
+
+        let path = super::new_day(&root, 11)?;
+        assert_eq!(path, root.join("day11"));
+        assert_eq!(
+            fs::read_to_string(path.join("Cargo.toml")).unwrap(),
+            "[package]\nname = \"day11\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("src").join("main.rs")).unwrap(),
+            "use day11::part1;\nfn main() { println!(\"day11\"); }\n"
+        );
+        for file in ["test1.txt", "test2.txt", "input.txt"] {
+            assert_eq!(fs::read_to_string(path.join("data").join(file)).unwrap(), "");
+        }
+
+        // Not commented in the manifest
+        super::new_day(&root, 3)?;
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            "[workspace]\nmembers = [\n\t\"day01\",\n\t\"day11\",\n\t\"day03\",\n]\n"
+        );
+
+        // Refuses to overwrite
+        assert!(matches!(
+            super::new_day(&root, 11),
+            Err(XtaskError::Usage { .. })
+        ));
+
+        fs::remove_dir_all(&root).map_err(io_error(&root))?;
+        Ok(())
+    }
+}